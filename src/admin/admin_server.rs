@@ -0,0 +1,288 @@
+use crate::admin::http::{HttpRequest, HttpResponse, read_request, write_response};
+use crate::config::admin_cfg::AdminCfg;
+use crate::config::backend_cfg::BackendCfg;
+use crate::config::validation::ValidationErrors;
+use crate::logging::events::exporter_event::ExporterEvent;
+use crate::state::backend::Backend;
+use crate::state::backend_state::BackendState;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::select;
+use tokio::sync::RwLock;
+use tokio::sync::mpsc::Sender;
+use tokio::task::JoinSet;
+
+/// Point-in-time view of a [`Backend`] as returned by the admin API.
+#[derive(Debug, Serialize)]
+pub struct BackendSnapshot {
+    pub id: String,
+    pub address: String,
+    pub health_address: String,
+    pub max_connections: u64,
+    pub base_weight: u64,
+    pub current_weight: u64,
+    pub agent_weight_pct: u64,
+    pub active_conn: u64,
+    pub avg_latency_ms: usize,
+    pub state: BackendState,
+    pub consecutive_successes: u64,
+    pub consecutive_failures: u64,
+    pub breaker: String,
+}
+
+impl BackendSnapshot {
+    pub fn of(backend: &Backend) -> Self {
+        Self {
+            id: backend.id.clone(),
+            address: backend.addr.clone(),
+            health_address: backend.health_addr.read().unwrap().clone(),
+            max_connections: backend.max_conn.load(Ordering::Relaxed),
+            base_weight: backend.base_weight.load(Ordering::Relaxed),
+            current_weight: backend.current_weight.load(Ordering::Relaxed),
+            agent_weight_pct: backend.agent_weight_pct.load(Ordering::Relaxed),
+            active_conn: backend.active_conn.load(Ordering::Relaxed),
+            avg_latency_ms: backend.avg_latency_ms.load(Ordering::Relaxed),
+            state: backend.state(),
+            consecutive_successes: backend.consecutive_successes.load(Ordering::Relaxed),
+            consecutive_failures: backend.consecutive_failures.load(Ordering::Relaxed),
+            breaker: backend.breaker.state().to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct WeightBody {
+    weight: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum AdminState {
+    Active,
+    Drain,
+    Maintenance,
+}
+
+#[derive(Deserialize)]
+struct StateBody {
+    state: AdminState,
+}
+
+/// JSON API for inspecting and mutating the live backend pool.
+///
+/// Routes:
+/// - `GET /backends` lists every backend.
+/// - `GET /backends/{id}` shows one backend.
+/// - `POST /backends` adds a backend from a `BackendCfg` body.
+/// - `DELETE /backends/{id}` removes a backend; in-flight connections are left to finish.
+/// - `PUT /backends/{id}/weight` sets `{"weight": n}` as both base and current weight.
+/// - `PUT /backends/{id}/state` sets `{"state": "active" | "drain" | "maintenance"}`.
+#[derive(Clone)]
+pub struct AdminServer {
+    cfg: AdminCfg,
+    backends: Arc<RwLock<Vec<Arc<Backend>>>>,
+    admin_tx: Sender<ExporterEvent>,
+}
+
+impl AdminServer {
+    pub fn new(
+        cfg: AdminCfg,
+        backends: Arc<RwLock<Vec<Arc<Backend>>>>,
+        admin_tx: Sender<ExporterEvent>,
+    ) -> Self {
+        Self {
+            cfg,
+            backends,
+            admin_tx,
+        }
+    }
+
+    pub async fn serve(&self) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(&self.cfg.address).await?;
+        // Owned by this future, so aborting the server also aborts every open admin connection.
+        let mut connections = JoinSet::new();
+
+        loop {
+            let (stream, _) = select! {
+                // Reap finished connections so the set only tracks open ones.
+                Some(_) = connections.join_next(), if !connections.is_empty() => continue,
+                accepted = listener.accept() => accepted?,
+            };
+            let server = self.clone();
+
+            connections.spawn(async move {
+                if let Err(e) = server.handle(stream).await {
+                    let _ = server
+                        .admin_tx
+                        .send(ExporterEvent::Error {
+                            err: format!("Admin request failed: {}", e),
+                        })
+                        .await;
+                }
+            });
+        }
+    }
+
+    async fn handle(&self, mut stream: TcpStream) -> anyhow::Result<()> {
+        let res = match read_request(&mut stream).await {
+            Ok(req) => self.route(req).await,
+            Err(e) => HttpResponse::error(400, e),
+        };
+        write_response(&mut stream, res).await
+    }
+
+    async fn route(&self, req: HttpRequest) -> HttpResponse {
+        let segments: Vec<&str> = req
+            .path
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        match (req.method.as_str(), segments.as_slice()) {
+            ("GET", ["backends"]) => self.list().await,
+            ("POST", ["backends"]) => self.add(&req.body).await,
+            ("GET", ["backends", id]) => self.show(id).await,
+            ("DELETE", ["backends", id]) => self.remove(id).await,
+            ("PUT", ["backends", id, "weight"]) => self.set_weight(id, &req.body).await,
+            ("PUT", ["backends", id, "state"]) => self.set_state(id, &req.body).await,
+            (_, ["backends", ..]) => HttpResponse::error(405, "method not allowed"),
+            _ => HttpResponse::error(404, "not found"),
+        }
+    }
+
+    async fn find(&self, id: &str) -> Option<Arc<Backend>> {
+        let rg = self.backends.read().await;
+        rg.iter().find(|b| b.id == id).cloned()
+    }
+
+    async fn list(&self) -> HttpResponse {
+        let rg = self.backends.read().await;
+        let snapshots: Vec<BackendSnapshot> = rg.iter().map(|b| BackendSnapshot::of(b)).collect();
+        HttpResponse::json(200, &json!(snapshots))
+    }
+
+    async fn show(&self, id: &str) -> HttpResponse {
+        match self.find(id).await {
+            Some(backend) => HttpResponse::json(200, &json!(BackendSnapshot::of(&backend))),
+            None => HttpResponse::error(404, format!("backend {} not found", id)),
+        }
+    }
+
+    async fn add(&self, body: &[u8]) -> HttpResponse {
+        let cfg: BackendCfg = match serde_json::from_slice(body) {
+            Ok(cfg) => cfg,
+            Err(e) => return HttpResponse::error(400, e),
+        };
+
+        // Backends added here must pass the same checks as those in the config file.
+        let mut errors = ValidationErrors::default();
+        cfg.validate("body", &mut errors);
+        if !errors.is_empty() {
+            return HttpResponse::json(
+                400,
+                &json!({ "error": errors.to_string(), "errors": errors.messages() }),
+            );
+        }
+
+        let backend = Arc::new(Backend::from_cfg(&cfg));
+        {
+            let mut wg = self.backends.write().await;
+            if wg.iter().any(|b| b.id == cfg.id) {
+                return HttpResponse::error(409, format!("backend {} already exists", cfg.id));
+            }
+            if wg.iter().any(|b| b.addr == cfg.address) {
+                return HttpResponse::error(
+                    409,
+                    format!("a backend with address {} already exists", cfg.address),
+                );
+            }
+            wg.push(backend.clone());
+        }
+
+        self.emit(ExporterEvent::BackendAdded {
+            id: backend.id.clone(),
+            addr: backend.addr.clone(),
+        })
+        .await;
+        HttpResponse::json(201, &json!(BackendSnapshot::of(&backend)))
+    }
+
+    async fn remove(&self, id: &str) -> HttpResponse {
+        let removed = {
+            let mut wg = self.backends.write().await;
+            wg.iter().position(|b| b.id == id).map(|idx| wg.remove(idx))
+        };
+
+        match removed {
+            Some(backend) => {
+                self.emit(ExporterEvent::BackendRemoved {
+                    id: backend.id.clone(),
+                    addr: backend.addr.clone(),
+                })
+                .await;
+                HttpResponse::json(200, &json!(BackendSnapshot::of(&backend)))
+            }
+            None => HttpResponse::error(404, format!("backend {} not found", id)),
+        }
+    }
+
+    async fn set_weight(&self, id: &str, body: &[u8]) -> HttpResponse {
+        let body: WeightBody = match serde_json::from_slice(body) {
+            Ok(body) => body,
+            Err(e) => return HttpResponse::error(400, e),
+        };
+        let Some(backend) = self.find(id).await else {
+            return HttpResponse::error(404, format!("backend {} not found", id));
+        };
+
+        // The base weight moves too, otherwise health recovery would pull the backend back to its old value.
+        backend.base_weight.store(body.weight, Ordering::Relaxed);
+        let old = backend.current_weight.swap(body.weight, Ordering::Relaxed);
+
+        self.emit(ExporterEvent::WeightSet {
+            id: backend.id.clone(),
+            old,
+            new: body.weight,
+        })
+        .await;
+        HttpResponse::json(200, &json!(BackendSnapshot::of(&backend)))
+    }
+
+    async fn set_state(&self, id: &str, body: &[u8]) -> HttpResponse {
+        let body: StateBody = match serde_json::from_slice(body) {
+            Ok(body) => body,
+            Err(e) => return HttpResponse::error(400, e),
+        };
+        let Some(backend) = self.find(id).await else {
+            return HttpResponse::error(404, format!("backend {} not found", id));
+        };
+
+        // Returning to rotation starts from booting and lets the next health check settle the state.
+        let new = match body.state {
+            AdminState::Active => BackendState::Booting,
+            AdminState::Drain => BackendState::Draining,
+            AdminState::Maintenance => BackendState::Maintenance,
+        };
+        let old = backend.set_state(new);
+
+        if old != new {
+            self.emit(ExporterEvent::StateChanged {
+                addr: backend.addr.clone(),
+                old,
+                new,
+            })
+            .await;
+        }
+        HttpResponse::json(200, &json!(BackendSnapshot::of(&backend)))
+    }
+
+    async fn emit(&self, event: ExporterEvent) {
+        let _ = self.admin_tx.send(event).await;
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::{Context, bail};
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// Time a client gets to send its whole request, so idle or slow clients cannot hold a connection open.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The subset of an HTTP/1.1 request the admin API needs.
+#[derive(Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn json(status: u16, body: &serde_json::Value) -> Self {
+        Self {
+            status,
+            body: body.to_string(),
+        }
+    }
+
+    pub fn error(status: u16, err: impl std::fmt::Display) -> Self {
+        Self::json(status, &serde_json::json!({ "error": err.to_string() }))
+    }
+}
+
+pub async fn read_request(stream: &mut TcpStream) -> anyhow::Result<HttpRequest> {
+    match timeout(READ_TIMEOUT, read_request_inner(stream)).await {
+        Ok(req) => req,
+        Err(_) => bail!("request not received within {:?}", READ_TIMEOUT),
+    }
+}
+
+async fn read_request_inner(stream: &mut TcpStream) -> anyhow::Result<HttpRequest> {
+    // The head is read through a capped reader so a client that never sends a newline cannot
+    // make us buffer without limit; the cap is lifted to the body length once the head is parsed.
+    let mut reader = BufReader::new(stream.take(MAX_HEAD_BYTES as u64));
+
+    let mut request_line = String::new();
+    read_head_line(&mut reader, &mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        bail!("malformed request line");
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        let read = read_head_line(&mut reader, &mut line).await?;
+        if read == 0 || line == "\r\n" || line == "\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().context("invalid content-length")?;
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        bail!("request body exceeds {} bytes", MAX_BODY_BYTES);
+    }
+    reader.get_mut().set_limit(content_length as u64);
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    Ok(HttpRequest { method, path, body })
+}
+
+/// Reads one head line; a line cut off without a newline means the head hit `MAX_HEAD_BYTES` or EOF.
+async fn read_head_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    line: &mut String,
+) -> anyhow::Result<usize> {
+    let read = reader.read_line(line).await?;
+    if read > 0 && !line.ends_with('\n') {
+        bail!(
+            "request head is truncated or exceeds {} bytes",
+            MAX_HEAD_BYTES
+        );
+    }
+    Ok(read)
+}
+
+pub async fn write_response(stream: &mut TcpStream, res: HttpResponse) -> anyhow::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        res.status,
+        reason(res.status),
+        res.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(res.body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        _ => "Internal Server Error",
+    }
+}
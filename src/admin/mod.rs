@@ -0,0 +1,2 @@
+pub mod admin_server;
+pub mod http;
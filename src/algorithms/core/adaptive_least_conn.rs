@@ -1,7 +1,7 @@
 use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
 use crate::state::backend::Backend;
-use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 pub struct AdaptiveLeastConn;
 
@@ -11,21 +11,29 @@ impl AdaptiveLeastConn {
     }
 }
 
+impl Default for AdaptiveLeastConn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LoadBalancingAlgorithm for AdaptiveLeastConn {
-    fn select_backend(&self, eligible_candidates: &Vec<Arc<Backend>>) -> Option<usize> {
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize> {
         eligible_candidates
             .iter()
             .enumerate()
-            .min_by_key(|(_, backend)| {
+            .map(|(idx, backend)| {
                 let active_conn = backend.active_conn.load(Ordering::Relaxed);
                 let avg_latency = backend.avg_latency_ms.load(Ordering::Relaxed);
 
                 // Adaptive scoring: combine connection count and latency
                 // Lower score is better. We weight connections more heavily than latency.
-                // Formula: active_conn * 1000 + avg_latency
+                // Formula: (active_conn + 1) * 1000 + avg_latency, divided by the slow-start share
                 // This ensures that a backend with fewer connections is preferred even with slightly higher latency
-                (active_conn as usize) * 1000 + avg_latency
+                let score = (active_conn as usize + 1) * 1000 + avg_latency;
+                (idx, score as f64 / backend.traffic_share())
             })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
             .map(|(idx, _)| idx)
     }
 }
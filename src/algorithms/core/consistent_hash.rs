@@ -0,0 +1,130 @@
+use crate::algorithms::hashing::{self, Members, candidate_index, first_admitted, hash_ip, hash64};
+use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
+use crate::state::backend::Backend;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, RwLock};
+
+/// Average number of points per backend; weights only decide how they are shared out, so the
+/// ring size does not grow with the weights.
+const VNODES_PER_BACKEND: u64 = 160;
+
+/// A hash ring over a backend pool, with virtual nodes proportional to `base_weight`.
+pub struct HashRing {
+    members: Members,
+    /// Sorted `(hash, pool index)` points.
+    points: Vec<(u64, usize)>,
+}
+
+impl HashRing {
+    pub fn build(pool: &[Arc<Backend>]) -> Self {
+        let members = Members::of(pool);
+        let total_weight: u128 = members.iter().map(|(_, w)| *w.max(&1) as u128).sum();
+        let total_points = VNODES_PER_BACKEND as u128 * members.len() as u128;
+
+        let mut points = Vec::new();
+        for (idx, (id, weight)) in members.iter().enumerate() {
+            let vnodes = (*weight.max(&1) as u128 * total_points / total_weight).max(1);
+            for vnode in 0..vnodes {
+                let hash = hash64(format!("{}#{}", id, vnode).as_bytes(), 0);
+                points.push((hash, idx));
+            }
+        }
+        points.sort_unstable();
+        Self { members, points }
+    }
+
+    /// Pool indices in ring order starting at the first point at or after `key`, each once.
+    pub fn walk(&self, key: u64) -> impl Iterator<Item = usize> + '_ {
+        let start = self.points.partition_point(|(hash, _)| *hash < key);
+        let mut seen = vec![false; self.members.len()];
+        self.points[start..]
+            .iter()
+            .chain(&self.points[..start])
+            .filter_map(move |(_, idx)| {
+                let first = !std::mem::replace(&mut seen[*idx], true);
+                first.then_some(*idx)
+            })
+    }
+}
+
+/// Client-affine selection: the client IP is hashed onto a ring of the configured backends and
+/// the first eligible one clockwise is chosen, so a backend leaving rotation, briefly full or
+/// removed only moves the clients on its own arcs of the ring.
+///
+/// With a bounded load, a backend already holding more than `(1 + epsilon)` times the average
+/// `active_conn` is skipped and the walk continues clockwise, so hot clients spill over to the
+/// next backends on the ring instead of overloading one.
+pub struct ConsistentHash {
+    ring: RwLock<Option<Arc<HashRing>>>,
+    epsilon: Option<f64>,
+}
+
+impl ConsistentHash {
+    pub fn new() -> Self {
+        Self {
+            ring: RwLock::new(None),
+            epsilon: None,
+        }
+    }
+
+    pub fn with_bounded_load(epsilon: f64) -> Self {
+        Self {
+            ring: RwLock::new(None),
+            epsilon: Some(epsilon),
+        }
+    }
+}
+
+impl Default for ConsistentHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoadBalancingAlgorithm for ConsistentHash {
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize> {
+        self.select_backend_for(
+            eligible_candidates,
+            eligible_candidates,
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        )
+    }
+
+    fn select_backend_for(
+        &self,
+        pool: &[Arc<Backend>],
+        eligible_candidates: &[Arc<Backend>],
+        client: IpAddr,
+    ) -> Option<usize> {
+        if eligible_candidates.is_empty() {
+            return None;
+        }
+
+        // The ring is only rebuilt when the pool or its weights change.
+        let ring = hashing::cached(&self.ring, pool, |r| &r.members, HashRing::build);
+        let key = hash_ip(client);
+        let walk = ring
+            .walk(key)
+            .filter_map(|idx| candidate_index(pool, idx, eligible_candidates));
+
+        let Some(epsilon) = self.epsilon else {
+            return first_admitted(walk, eligible_candidates, key);
+        };
+        // The new connection counts towards the average, so an idle pool never rejects anyone.
+        let total: u64 = eligible_candidates
+            .iter()
+            .map(|b| b.active_conn.load(Ordering::Relaxed))
+            .sum();
+        let average = (total + 1) as f64 / eligible_candidates.len() as f64;
+        let capacity = ((1.0 + epsilon) * average).ceil() as u64;
+
+        let below_capacity = walk.filter(|idx| {
+            eligible_candidates[*idx]
+                .active_conn
+                .load(Ordering::Relaxed)
+                < capacity
+        });
+        first_admitted(below_capacity, eligible_candidates, key)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
 use crate::state::backend::Backend;
-use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 pub struct LeastConn;
 
@@ -11,12 +11,23 @@ impl LeastConn {
     }
 }
 
+impl Default for LeastConn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LoadBalancingAlgorithm for LeastConn {
-    fn select_backend(&self, eligible_candidates: &Vec<Arc<Backend>>) -> Option<usize> {
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize> {
         eligible_candidates
             .iter()
             .enumerate()
-            .min_by_key(|(_, backend)| backend.active_conn.load(Ordering::Relaxed))
+            .map(|(idx, backend)| {
+                // Counting the new connection keeps an idle slow-starting backend from always winning.
+                let active_conn = backend.active_conn.load(Ordering::Relaxed) + 1;
+                (idx, active_conn as f64 / backend.traffic_share())
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
             .map(|(idx, _)| idx)
     }
 }
@@ -0,0 +1,109 @@
+use crate::algorithms::hashing::{self, Members, candidate_index, first_admitted, hash_ip, hash64};
+use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
+use crate::state::backend::Backend;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::{Arc, RwLock};
+
+/// Lookup table size; a prime well above the number of backends keeps the spread even.
+const TABLE_SIZE: usize = 65_537;
+
+/// Maglev lookup table mapping every slot to a pool index.
+struct Table {
+    members: Members,
+    slots: Vec<usize>,
+}
+
+impl Table {
+    /// Fills the table by letting each backend claim slots along its own permutation, taking
+    /// `base_weight` turns per round so shares follow the weights.
+    fn build(pool: &[Arc<Backend>]) -> Self {
+        let members = Members::of(pool);
+        let m = TABLE_SIZE as u64;
+
+        let permutations: Vec<(u64, u64)> = members
+            .iter()
+            .map(|(id, _)| {
+                let offset = hash64(id.as_bytes(), 0x6d61_676c) % m;
+                let skip = hash64(id.as_bytes(), 0x6576_0001) % (m - 1) + 1;
+                (offset, skip)
+            })
+            .collect();
+        let turns: Vec<u64> = members.iter().map(|(_, weight)| *weight.max(&1)).collect();
+
+        let mut slots = vec![usize::MAX; TABLE_SIZE];
+        let mut next = vec![0u64; members.len()];
+        let mut filled = 0;
+        'fill: loop {
+            for (idx, (offset, skip)) in permutations.iter().enumerate() {
+                for _ in 0..turns[idx] {
+                    let mut slot = ((offset + next[idx] * skip) % m) as usize;
+                    while slots[slot] != usize::MAX {
+                        next[idx] += 1;
+                        slot = ((offset + next[idx] * skip) % m) as usize;
+                    }
+                    slots[slot] = idx;
+                    next[idx] += 1;
+                    filled += 1;
+                    if filled == TABLE_SIZE {
+                        break 'fill;
+                    }
+                }
+            }
+        }
+
+        Self { members, slots }
+    }
+}
+
+/// Client-affine selection through a Maglev lookup table: O(1) per connection, near-even
+/// spread by weight, and little remapping when a backend joins or leaves.
+///
+/// The table is built from the configured pool and only rebuilt when it or a `base_weight`
+/// changes. A client whose slot names an ineligible backend moves on to the following slots,
+/// which belong to a mix of backends, so its load spreads out without remapping anyone else.
+pub struct Maglev {
+    table: RwLock<Option<Arc<Table>>>,
+}
+
+impl Maglev {
+    pub fn new() -> Self {
+        Self {
+            table: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for Maglev {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoadBalancingAlgorithm for Maglev {
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize> {
+        self.select_backend_for(
+            eligible_candidates,
+            eligible_candidates,
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        )
+    }
+
+    fn select_backend_for(
+        &self,
+        pool: &[Arc<Backend>],
+        eligible_candidates: &[Arc<Backend>],
+        client: IpAddr,
+    ) -> Option<usize> {
+        if eligible_candidates.is_empty() {
+            return None;
+        }
+
+        let table = hashing::cached(&self.table, pool, |t| &t.members, Table::build);
+        let key = hash_ip(client);
+        let first = (key % TABLE_SIZE as u64) as usize;
+        let probe = (0..TABLE_SIZE)
+            .map(|step| table.slots[(first + step) % TABLE_SIZE])
+            .filter_map(|idx| candidate_index(pool, idx, eligible_candidates));
+        first_admitted(probe, eligible_candidates, key)
+    }
+}
@@ -1,4 +1,8 @@
 pub mod adaptive_least_conn;
+pub mod consistent_hash;
 pub mod least_conn;
+pub mod maglev;
+pub mod p2c;
+pub mod peak_ewma;
 pub mod round_robin;
 pub mod weighted_round_robin;
@@ -0,0 +1,56 @@
+use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
+use crate::config::algorithm_cfg::P2cScore;
+use crate::state::backend::Backend;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+/// Power of two choices: samples two distinct eligible backends at random and takes the one
+/// with the lower score. Unlike a full least-conn scan, concurrent selections rarely agree on
+/// the same minimum, so they do not herd onto one backend.
+pub struct P2c {
+    score: P2cScore,
+}
+
+impl P2c {
+    pub fn new(score: P2cScore) -> Self {
+        Self { score }
+    }
+
+    fn score(&self, backend: &Backend) -> f64 {
+        let active_conn = backend.active_conn.load(Ordering::Relaxed);
+        let latency = backend.avg_latency_ms.load(Ordering::Relaxed) as u64;
+        let score = match self.score {
+            // The new connection counts, so an idle slow-starting backend is still penalised.
+            P2cScore::Connections => active_conn + 1,
+            P2cScore::Latency => latency.max(1),
+            // Latency multiplied by outstanding connections; the +1 keeps idle backends comparable.
+            P2cScore::Combined => (active_conn + 1) * latency.max(1),
+        };
+        score as f64 / backend.traffic_share()
+    }
+}
+
+impl Default for P2c {
+    fn default() -> Self {
+        Self::new(P2cScore::default())
+    }
+}
+
+impl LoadBalancingAlgorithm for P2c {
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize> {
+        let len = eligible_candidates.len();
+        if len < 2 {
+            return (len == 1).then_some(0);
+        }
+
+        let first = rand::random_range(0..len);
+        // Offsetting by 1..len guarantees a second, different backend.
+        let second = (first + rand::random_range(1..len)) % len;
+
+        if self.score(&eligible_candidates[second]) < self.score(&eligible_candidates[first]) {
+            Some(second)
+        } else {
+            Some(first)
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
+use crate::config::algorithm_cfg::AlgorithmOptionsCfg;
+use crate::state::backend::Backend;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// Lowest latency used for the cost, so backends with no measurement yet are still told apart by
+/// their outstanding connections instead of all tying at 0.
+const MIN_LATENCY_MS: f64 = 0.001;
+
+/// Routes to the lowest expected wait: the backend's Peak-EWMA latency from real connections,
+/// multiplied by its outstanding connections plus the new one.
+pub struct PeakEwma {
+    decay: Duration,
+}
+
+impl PeakEwma {
+    pub fn new(decay: Duration) -> Self {
+        Self { decay }
+    }
+
+    fn cost(&self, backend: &Backend) -> f64 {
+        // Until a proxied connection has been measured, the health probe latency stands in.
+        let latency = backend
+            .latency
+            .estimate_ms(self.decay)
+            .unwrap_or(backend.avg_latency_ms.load(Ordering::Relaxed) as f64)
+            .max(MIN_LATENCY_MS);
+        let active_conn = backend.active_conn.load(Ordering::Relaxed);
+        latency * (active_conn + 1) as f64 / backend.traffic_share()
+    }
+}
+
+impl Default for PeakEwma {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(
+            AlgorithmOptionsCfg::default().peak_ewma_decay_ms,
+        ))
+    }
+}
+
+impl LoadBalancingAlgorithm for PeakEwma {
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize> {
+        eligible_candidates
+            .iter()
+            .map(|backend| self.cost(backend))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(idx, _)| idx)
+    }
+
+    fn observe_latency(&self, backend: &Backend, latency: Duration) {
+        backend.latency.observe(latency, self.decay);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
 use crate::state::backend::Backend;
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub struct RoundRobin {
     cursor: AtomicUsize,
@@ -15,13 +15,29 @@ impl RoundRobin {
     }
 }
 
+impl Default for RoundRobin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LoadBalancingAlgorithm for RoundRobin {
-    fn select_backend(&self, eligible_candidates: &Vec<Arc<Backend>>) -> Option<usize> {
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize> {
         if eligible_candidates.is_empty() {
             return None;
         }
 
-        let idx = self.cursor.fetch_add(1, Ordering::Relaxed);
-        Some(idx % eligible_candidates.len())
+        // A slow-starting backend passes its turn on to the next one with probability
+        // `1 - traffic_share`, so it gets its share of the rotation.
+        let len = eligible_candidates.len();
+        let mut idx = self.cursor.fetch_add(1, Ordering::Relaxed) % len;
+        for _ in 0..len {
+            let share = eligible_candidates[idx].traffic_share();
+            if share >= 1.0 || rand::random::<f64>() < share {
+                break;
+            }
+            idx = self.cursor.fetch_add(1, Ordering::Relaxed) % len;
+        }
+        Some(idx)
     }
 }
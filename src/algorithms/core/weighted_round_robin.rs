@@ -1,7 +1,7 @@
 use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
 use crate::state::backend::Backend;
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub struct WeightedRoundRobin {
     cursor: AtomicUsize,
@@ -15,15 +15,21 @@ impl WeightedRoundRobin {
     }
 }
 
+impl Default for WeightedRoundRobin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LoadBalancingAlgorithm for WeightedRoundRobin {
-    fn select_backend(&self, eligible_candidates: &Vec<Arc<Backend>>) -> Option<usize> {
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize> {
         if eligible_candidates.is_empty() {
             return None;
         }
 
         let total_weight: u64 = eligible_candidates
             .iter()
-            .map(|backend| backend.current_weight.load(Ordering::Relaxed))
+            .map(|backend| backend.effective_weight())
             .sum();
 
         if total_weight == 0 {
@@ -36,7 +42,7 @@ impl LoadBalancingAlgorithm for WeightedRoundRobin {
         let mut current_weight = (idx as u64) % total_weight;
 
         for (i, backend) in eligible_candidates.iter().enumerate() {
-            let backend_weight = backend.current_weight.load(Ordering::Relaxed);
+            let backend_weight = backend.effective_weight();
             if backend_weight == 0 {
                 continue;
             }
@@ -1,20 +1,37 @@
 use crate::algorithms::core::adaptive_least_conn::AdaptiveLeastConn;
+use crate::algorithms::core::consistent_hash::ConsistentHash;
 use crate::algorithms::core::least_conn::LeastConn;
+use crate::algorithms::core::maglev::Maglev;
+use crate::algorithms::core::p2c::P2c;
+use crate::algorithms::core::peak_ewma::PeakEwma;
 use crate::algorithms::core::round_robin::RoundRobin;
 use crate::algorithms::core::weighted_round_robin::WeightedRoundRobin;
 use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
-use crate::config::algorithm_cfg::AlgorithmType;
+use crate::config::algorithm_cfg::{AlgorithmOptionsCfg, AlgorithmType};
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct Algorithm;
 
 impl Algorithm {
-    pub fn select(cfg: AlgorithmType) -> Arc<dyn LoadBalancingAlgorithm> {
+    pub fn select(
+        cfg: AlgorithmType,
+        options: &AlgorithmOptionsCfg,
+    ) -> Arc<dyn LoadBalancingAlgorithm> {
         match cfg {
             AlgorithmType::RoundRobin => Arc::new(RoundRobin::new()),
             AlgorithmType::LeastConn => Arc::new(LeastConn::new()),
             AlgorithmType::AdaptiveLeastConn => Arc::new(AdaptiveLeastConn::new()),
             AlgorithmType::WeightedRoundRobin => Arc::new(WeightedRoundRobin::new()),
+            AlgorithmType::ConsistentHash => Arc::new(ConsistentHash::new()),
+            AlgorithmType::Maglev => Arc::new(Maglev::new()),
+            AlgorithmType::BoundedConsistentHash => Arc::new(ConsistentHash::with_bounded_load(
+                options.bounded_load_epsilon,
+            )),
+            AlgorithmType::P2c => Arc::new(P2c::new(options.p2c_score)),
+            AlgorithmType::PeakEwma => Arc::new(PeakEwma::new(Duration::from_millis(
+                options.peak_ewma_decay_ms,
+            ))),
         }
     }
 }
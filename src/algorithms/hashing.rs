@@ -0,0 +1,121 @@
+use crate::state::backend::Backend;
+use std::net::IpAddr;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, RwLock};
+
+/// 64-bit FNV-1a followed by a SplitMix64 finalizer, stable across builds and restarts so a
+/// client keeps its backend when the balancer is redeployed.
+pub fn hash64(bytes: &[u8], seed: u64) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64 ^ seed;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+/// Hash of a client address; IPv4-mapped IPv6 addresses hash like their IPv4 form.
+pub fn hash_ip(ip: IpAddr) -> u64 {
+    match ip.to_canonical() {
+        IpAddr::V4(v4) => hash64(&v4.octets(), 0),
+        IpAddr::V6(v6) => hash64(&v6.octets(), 0),
+    }
+}
+
+/// `(id, base_weight)` of a backend pool, in order; hash-based algorithms keep it next to the
+/// structure they built so it is only rebuilt when the pool or its weights change.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Members(Vec<(String, u64)>);
+
+impl Members {
+    pub fn of(pool: &[Arc<Backend>]) -> Self {
+        Self(
+            pool.iter()
+                .map(|b| (b.id.clone(), b.base_weight.load(Ordering::Relaxed)))
+                .collect(),
+        )
+    }
+
+    pub fn matches(&self, pool: &[Arc<Backend>]) -> bool {
+        self.0.len() == pool.len()
+            && self.0.iter().zip(pool).all(|((id, weight), b)| {
+                *id == b.id && *weight == b.base_weight.load(Ordering::Relaxed)
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(String, u64)> {
+        self.0.iter()
+    }
+}
+
+/// Position of `pool[pool_idx]` among `eligible_candidates`, if it is eligible.
+pub fn candidate_index(
+    pool: &[Arc<Backend>],
+    pool_idx: usize,
+    eligible_candidates: &[Arc<Backend>],
+) -> Option<usize> {
+    let backend = pool.get(pool_idx)?;
+    eligible_candidates
+        .iter()
+        .position(|c| Arc::ptr_eq(c, backend))
+}
+
+/// Returns the structure cached in `cache` if it was built from `pool`, building and caching it
+/// otherwise. Lookups only hold the read lock long enough to clone the `Arc`.
+pub fn cached<T>(
+    cache: &RwLock<Option<Arc<T>>>,
+    pool: &[Arc<Backend>],
+    members: impl Fn(&T) -> &Members,
+    build: impl FnOnce(&[Arc<Backend>]) -> T,
+) -> Arc<T> {
+    if let Some(built) = cache.read().unwrap().as_ref()
+        && members(built).matches(pool)
+    {
+        return built.clone();
+    }
+
+    let mut cache = cache.write().unwrap();
+    // Another selection may have rebuilt it while this one waited for the write lock.
+    if let Some(built) = cache.as_ref()
+        && members(built).matches(pool)
+    {
+        return built.clone();
+    }
+    let built = Arc::new(build(pool));
+    *cache = Some(built.clone());
+    built
+}
+
+/// Whether a slow-starting `backend` takes the client hashed to `key`. Each client gets a fixed
+/// draw per backend, so as the share grows clients only move onto the backend, never away.
+pub fn admits(backend: &Backend, key: u64) -> bool {
+    let share = backend.traffic_share();
+    share >= 1.0 || (hash64(backend.id.as_bytes(), key) as f64 / u64::MAX as f64) < share
+}
+
+/// The first of `candidates` (in preference order) that admits `key`, or the first one at all
+/// when every backend is slow-starting and none does.
+pub fn first_admitted(
+    mut candidates: impl Iterator<Item = usize>,
+    eligible_candidates: &[Arc<Backend>],
+    key: u64,
+) -> Option<usize> {
+    let first = candidates.next()?;
+    if admits(&eligible_candidates[first], key) {
+        return Some(first);
+    }
+    candidates
+        .find(|idx| admits(&eligible_candidates[*idx], key))
+        .or(Some(first))
+}
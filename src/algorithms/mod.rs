@@ -1,3 +1,4 @@
 pub mod core;
 pub mod factories;
+pub mod hashing;
 pub mod traits;
@@ -1,6 +1,25 @@
-use std::sync::Arc;
 use crate::state::backend::Backend;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
 
 pub trait LoadBalancingAlgorithm: Send + Sync {
-    fn select_backend(&self, eligible_candidates: &Vec<Arc<Backend>>) -> Option<usize>;
+    fn select_backend(&self, eligible_candidates: &[Arc<Backend>]) -> Option<usize>;
+
+    /// Selects a backend for a specific client among `eligible_candidates`, which are drawn from
+    /// `pool`, every backend currently configured. Algorithms with client affinity build their
+    /// lookup structure from `pool`, so backends moving in and out of eligibility neither force a
+    /// rebuild nor remap other clients; the rest ignore `pool` and the address.
+    fn select_backend_for(
+        &self,
+        _pool: &[Arc<Backend>],
+        eligible_candidates: &[Arc<Backend>],
+        _client: IpAddr,
+    ) -> Option<usize> {
+        self.select_backend(eligible_candidates)
+    }
+
+    /// Receives how long a proxied connection took from connecting to the backend's first byte;
+    /// only latency-aware algorithms keep it.
+    fn observe_latency(&self, _backend: &Backend, _latency: Duration) {}
 }
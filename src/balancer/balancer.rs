@@ -1,76 +1,393 @@
 use crate::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
+use crate::config::retry_cfg::RetryCfg;
+use crate::config::thresholds_cfg::ThresholdsCfg;
+use crate::logging::events::exporter_event::ExporterEvent;
 use crate::state::backend::Backend;
+use crate::state::circuit_breaker::{Admission, BreakerState};
 use anyhow::bail;
-use std::sync::Arc;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
 use std::sync::atomic::Ordering;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
-use tokio::io::{copy, split};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, split};
 use tokio::net::TcpStream;
 use tokio::sync::RwLock;
-use tokio::time::sleep;
+use tokio::sync::mpsc::Sender;
+use tokio::time::{Instant, sleep_until, timeout_at};
 use tokio::try_join;
 
 #[derive(Clone)]
 pub struct Balancer {
     active_backends: Arc<RwLock<Vec<Arc<Backend>>>>,
-    algorithm: Arc<dyn LoadBalancingAlgorithm>,
+    algorithm: Arc<RwLock<Arc<dyn LoadBalancingAlgorithm>>>,
+    retry: Arc<RwLock<RetryCfg>>,
+    thresholds: Arc<RwLock<ThresholdsCfg>>,
+    balancer_tx: Sender<ExporterEvent>,
 }
 
 impl Balancer {
     pub fn new(
         algorithm: Arc<dyn LoadBalancingAlgorithm>,
         active_backends: Arc<RwLock<Vec<Arc<Backend>>>>,
+        retry: RetryCfg,
+        thresholds: ThresholdsCfg,
+        balancer_tx: Sender<ExporterEvent>,
     ) -> Self {
         Self {
-            algorithm,
+            algorithm: Arc::new(RwLock::new(algorithm)),
             active_backends,
+            retry: Arc::new(RwLock::new(retry)),
+            thresholds: Arc::new(RwLock::new(thresholds)),
+            balancer_tx,
         }
     }
 
+    /// Swaps the routing strategy, retry policy and thresholds; connections already routed are unaffected.
+    pub async fn reconfigure(
+        &self,
+        algorithm: Arc<dyn LoadBalancingAlgorithm>,
+        retry: RetryCfg,
+        thresholds: ThresholdsCfg,
+    ) {
+        *self.algorithm.write().await = algorithm;
+        *self.retry.write().await = retry;
+        *self.thresholds.write().await = thresholds;
+    }
+
     pub async fn route_connection(&self, client: TcpStream) -> anyhow::Result<()> {
-        let candidates = self.get_eligible_candidates().await;
-        let mut selected = self.algorithm.select_backend(&candidates);
-
-        // [todo]: need to make these things configurable.
-        let mut retry_count = 0;
-        let max_retries = 3;
-        let retry_delay = Duration::from_millis(100);
-
-        while selected.is_none() && retry_count < max_retries {
-            sleep(retry_delay).await;
-            let candidates = self.get_eligible_candidates().await;
-            selected = self.algorithm.select_backend(&candidates);
-            retry_count += 1;
+        // Snapshot the settings so a reload mid-connection cannot mix two policies.
+        let algorithm = self.algorithm.read().await.clone();
+        let retry = self.retry.read().await.clone();
+        let thresholds = self.thresholds.read().await.clone();
+        let trials = thresholds.breaker_half_open_trials;
+        let deadline = (retry.deadline_ms > 0)
+            .then(|| Instant::now() + Duration::from_millis(retry.deadline_ms));
+
+        // Backends that refused the connection for this client, so failover moves on to the next
+        // one; cleared once no other backend is left.
+        let mut excluded: Vec<Arc<Backend>> = Vec::new();
+        // Backends found full or without a free half-open trial slot; they are only passed over
+        // until the next backoff, as slots may have been freed by then.
+        let mut skipped: Vec<Arc<Backend>> = Vec::new();
+        let mut attempt = 0;
+        let client_ip = client
+            .peer_addr()
+            .map_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED), |addr| addr.ip());
+
+        loop {
+            let (pool, candidates) = self
+                .get_eligible_candidates(&excluded, &skipped, &thresholds)
+                .await;
+
+            let backend = match algorithm.select_backend_for(&pool, &candidates, client_ip) {
+                Some(i) => candidates[i].clone(),
+                None => {
+                    // Every backend left has already refused this client. A backoff was just
+                    // taken, so try them again rather than giving up on a backend that restarts.
+                    if !excluded.is_empty() {
+                        excluded.clear();
+                        continue;
+                    }
+                    attempt += 1;
+                    if attempt >= retry.max_attempts {
+                        bail!("No available backends after {} attempts", attempt);
+                    }
+                    skipped.clear();
+                    Self::backoff(&retry, attempt, deadline).await?;
+                    continue;
+                }
+            };
+
+            // Other connections may have taken the last free slot or half-open trial since the
+            // candidates were listed; both are claimed atomically and selection moves on if either fails.
+            let Some(slot) = backend.try_reserve() else {
+                skipped.push(backend);
+                continue;
+            };
+            let Some(admission) = backend.breaker.admit(trials) else {
+                drop(slot);
+                skipped.push(backend);
+                continue;
+            };
+            attempt += 1;
+
+            // Connecting before touching the client stream means no client bytes are consumed
+            // until a backend actually accepted, so a failed attempt is safe to retry elsewhere.
+            let started = Instant::now();
+            let err = match Self::connect(&backend.addr, deadline).await {
+                Ok(upstream) => {
+                    let setup = started.elapsed();
+                    // A trial is settled once the backend accepts, not when the stream ends, so
+                    // long-lived connections cannot hold every trial slot of a half-open breaker.
+                    self.settle_admission(&backend, true, admission, &thresholds)
+                        .await?;
+
+                    let result = self
+                        .perform_routing(client, upstream, |ttfb| {
+                            algorithm.observe_latency(&backend, setup + ttfb)
+                        })
+                        .await;
+                    drop(slot);
+
+                    // A client resetting or going away says nothing about the backend's health.
+                    let backend_ok = !matches!(&result, Err(e) if e.side == Side::Backend);
+                    self.record_outcome(&backend, backend_ok, &thresholds)
+                        .await?;
+                    return Ok(result?);
+                }
+                Err(e) => e,
+            };
+
+            drop(slot);
+            self.settle_admission(&backend, false, admission, &thresholds)
+                .await?;
+            self.record_outcome(&backend, false, &thresholds).await?;
+
+            self.balancer_tx
+                .send(ExporterEvent::ConnectFailed {
+                    addr: backend.addr.clone(),
+                    attempt,
+                    err: err.to_string(),
+                })
+                .await?;
+
+            if attempt >= retry.max_attempts {
+                bail!(
+                    "Failed to connect to a backend after {} attempts, last error: {}",
+                    attempt,
+                    err
+                );
+            }
+
+            let policy = retry.with_override(backend.retry.read().unwrap().as_ref());
+            excluded.push(backend);
+            skipped.clear();
+            Self::backoff(&policy, attempt, deadline).await?;
         }
+    }
 
-        let selected = match selected {
-            Some(i) => i,
-            None => bail!("No available backends after {} retries", max_retries),
+    /// Reports whether the backend accepted the connection to its circuit breaker, which only
+    /// acts on half-open trials.
+    async fn settle_admission(
+        &self,
+        backend: &Backend,
+        connected: bool,
+        admission: Admission,
+        thresholds: &ThresholdsCfg,
+    ) -> anyhow::Result<()> {
+        let transition = if connected {
+            backend
+                .breaker
+                .on_success(admission, thresholds.breaker_half_open_trials)
+        } else {
+            backend.breaker.on_failure(admission)
         };
+        if let Some(state) = transition {
+            // A backend let back in by its breaker ramps up like one returning from down.
+            if state == BreakerState::Closed {
+                backend.start_slow_start();
+            }
+            self.balancer_tx
+                .send(ExporterEvent::breaker_transition(
+                    backend.addr.clone(),
+                    state,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Feeds a proxied outcome to the backend's error window. Exceeding `error_rate_limit` ejects
+    /// the backend: its weight drops to 0 and its breaker opens, so the health monitor restores
+    /// it like any other outage.
+    async fn record_outcome(
+        &self,
+        backend: &Backend,
+        success: bool,
+        thresholds: &ThresholdsCfg,
+    ) -> anyhow::Result<()> {
+        let window = Duration::from_millis(thresholds.error_window_ms);
+        let (failures, total) = backend.errors.record(success, window);
 
-        let backend = candidates[selected].clone();
-        backend.active_conn.fetch_add(1, Ordering::Relaxed);
-        let result = self.perform_routing(client, backend.addr.clone()).await;
-        backend.active_conn.fetch_sub(1, Ordering::Relaxed);
+        if total < thresholds.error_min_samples
+            || (failures as f32 / total as f32) <= thresholds.error_rate_limit
+        {
+            return Ok(());
+        }
 
-        result
+        // A fresh window after ejection keeps old failures from re-ejecting the backend on recovery.
+        backend.errors.reset();
+        if backend.current_weight.swap(0, Ordering::Relaxed) > 0 {
+            self.balancer_tx
+                .send(ExporterEvent::BackendEjected {
+                    addr: backend.addr.clone(),
+                    failures,
+                    total,
+                })
+                .await?;
+        }
+        if let Some(state) = backend.breaker.trip() {
+            self.balancer_tx
+                .send(ExporterEvent::breaker_transition(
+                    backend.addr.clone(),
+                    state,
+                ))
+                .await?;
+        }
+        Ok(())
     }
 
-    async fn get_eligible_candidates(&self) -> Vec<Arc<Backend>> {
-        let rg = self.active_backends.read().await;
-        rg.iter()
-            .filter(|b| b.has_some_wight() && !b.is_max_conn_reached())
-            .map(|x| x.clone())
-            .collect()
+    async fn connect(addr: &str, deadline: Option<Instant>) -> anyhow::Result<TcpStream> {
+        match deadline {
+            Some(deadline) => match timeout_at(deadline, TcpStream::connect(addr)).await {
+                Ok(stream) => Ok(stream?),
+                Err(_) => bail!("retry deadline exceeded while connecting to {}", addr),
+            },
+            None => Ok(TcpStream::connect(addr).await?),
+        }
     }
 
-    async fn perform_routing(&self, client: TcpStream, backend_addr: String) -> anyhow::Result<()> {
-        let backend = TcpStream::connect(backend_addr).await?;
+    async fn backoff(
+        policy: &RetryCfg,
+        attempt: u64,
+        deadline: Option<Instant>,
+    ) -> anyhow::Result<()> {
+        let wake = Instant::now() + policy.delay(attempt);
+        if deadline.is_some_and(|deadline| wake >= deadline) {
+            bail!("Retry deadline exceeded after {} attempts", attempt);
+        }
+        sleep_until(wake).await;
+        Ok(())
+    }
+
+    /// Returns the whole pool and the backends in it that may take this connection.
+    async fn get_eligible_candidates(
+        &self,
+        excluded: &[Arc<Backend>],
+        skipped: &[Arc<Backend>],
+        thresholds: &ThresholdsCfg,
+    ) -> (Vec<Arc<Backend>>, Vec<Arc<Backend>>) {
+        let trials = thresholds.breaker_half_open_trials;
+        let pool = self.active_backends.read().await.clone();
+        let eligible: Vec<Arc<Backend>> = pool
+            .iter()
+            .filter(|b| b.has_some_wight() && !b.is_max_conn_reached() && b.state().is_routable())
+            .filter(|b| b.breaker.allows_traffic(trials))
+            .filter(|b| !excluded.iter().chain(skipped).any(|x| Arc::ptr_eq(x, b)))
+            .cloned()
+            .collect();
+
+        // Slow-starting backends keep their place in the eligible set, so affinity holds, and
+        // every algorithm scales their weight or load by the share refreshed here instead.
+        let window = Duration::from_millis(thresholds.slow_start_ms);
+        for backend in &eligible {
+            backend.refresh_traffic_share(window, thresholds.slow_start_curve);
+        }
+
+        (pool, eligible)
+    }
+
+    /// Proxies both directions until they finish. `on_first_byte` gets the backend's time to first
+    /// byte, counted from the client's first byte, or from connecting when the backend speaks first.
+    async fn perform_routing(
+        &self,
+        client: TcpStream,
+        backend: TcpStream,
+        on_first_byte: impl FnOnce(Duration),
+    ) -> Result<(), ProxyError> {
+        let connected = Instant::now();
+        let request_sent = OnceLock::new();
         let (mut cr, mut cw) = split(client);
         let (mut br, mut bw) = split(backend);
-        let client_to_backend = copy(&mut cr, &mut bw);
-        let backend_to_client = copy(&mut br, &mut cw);
+        let client_to_backend = Self::relay(&mut cr, Side::Client, &mut bw, Side::Backend, || {
+            let _ = request_sent.set(Instant::now());
+        });
+        let backend_to_client = Self::relay(&mut br, Side::Backend, &mut cw, Side::Client, || {
+            on_first_byte(request_sent.get().unwrap_or(&connected).elapsed())
+        });
         try_join!(client_to_backend, backend_to_client)?;
         Ok(())
     }
+
+    /// Copies `reader` into `writer` until EOF, calling `on_first_chunk` as soon as data first
+    /// arrives. The EOF is passed on by shutting `writer` down, so a peer waiting for the other
+    /// side to finish closes its end and the connection slot is released. An error is tagged with
+    /// the side of whichever stream failed.
+    async fn relay<R, W>(
+        reader: &mut R,
+        reader_side: Side,
+        writer: &mut W,
+        writer_side: Side,
+        on_first_chunk: impl FnOnce(),
+    ) -> Result<(), ProxyError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut buf = vec![0u8; 8 * 1024];
+        let n = reader
+            .read(&mut buf)
+            .await
+            .map_err(|e| ProxyError::new(reader_side, e))?;
+        if n > 0 {
+            on_first_chunk();
+            writer
+                .write_all(&buf[..n])
+                .await
+                .map_err(|e| ProxyError::new(writer_side, e))?;
+            loop {
+                let n = reader
+                    .read(&mut buf)
+                    .await
+                    .map_err(|e| ProxyError::new(reader_side, e))?;
+                if n == 0 {
+                    break;
+                }
+                writer
+                    .write_all(&buf[..n])
+                    .await
+                    .map_err(|e| ProxyError::new(writer_side, e))?;
+            }
+        }
+        // The peer may already have closed its side; that is not an error for this direction.
+        let _ = writer.shutdown().await;
+        Ok(())
+    }
+}
+
+/// One end of a proxied connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Client,
+    Backend,
+}
+
+/// An I/O error while proxying, tagged with the side whose stream failed so only backend
+/// failures count towards its error rate.
+#[derive(Debug)]
+struct ProxyError {
+    side: Side,
+    source: std::io::Error,
+}
+
+impl ProxyError {
+    fn new(side: Side, source: std::io::Error) -> Self {
+        Self { side, source }
+    }
+}
+
+impl fmt::Display for ProxyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.side {
+            Side::Client => write!(f, "client stream failed: {}", self.source),
+            Side::Backend => write!(f, "backend stream failed: {}", self.source),
+        }
+    }
+}
+
+impl std::error::Error for ProxyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
 }
@@ -1 +1,2 @@
-pub mod balancer;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod balancer;
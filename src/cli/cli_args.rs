@@ -0,0 +1,55 @@
+use crate::config::load_balancer_cfg::LoadBalancerCfg;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Command-line interface of the load balancer binary.
+#[derive(Debug, Parser)]
+#[command(version, about = "A TCP load balancer with health monitoring")]
+pub struct CliArgs {
+    /// Path to the YAML configuration file.
+    #[arg(
+        long,
+        short,
+        env = "CONFIG_PATH",
+        default_value = "config.yaml",
+        global = true
+    )]
+    pub config: PathBuf,
+
+    /// Path of the file events are appended to.
+    #[arg(long, default_value = "log.txt", global = true)]
+    pub log_file: PathBuf,
+
+    /// Overrides `balancer.port` from the configuration file.
+    #[arg(long, short, global = true)]
+    pub port: Option<u16>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Start the load balancer (default when no subcommand is given).
+    Run,
+    /// Parse and check the configuration, printing every problem found and exiting non-zero if any.
+    #[command(alias = "check")]
+    Validate,
+    /// Print the configuration after defaults and command-line overrides are applied.
+    PrintEffectiveConfig,
+}
+
+impl CliArgs {
+    /// Loads the configuration file and applies the command-line overrides on top of it.
+    pub async fn load_cfg(&self) -> anyhow::Result<LoadBalancerCfg> {
+        let mut cfg = LoadBalancerCfg::load_from(&self.config).await?;
+        self.apply_overrides(&mut cfg);
+        Ok(cfg)
+    }
+
+    pub fn apply_overrides(&self, cfg: &mut LoadBalancerCfg) {
+        if let Some(port) = self.port {
+            cfg.balancer_cfg.port = port;
+        }
+    }
+}
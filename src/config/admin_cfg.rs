@@ -0,0 +1,22 @@
+use crate::config::validation::{ValidationErrors, is_valid_address};
+use serde::{Deserialize, Serialize};
+
+/// Runtime administration endpoint, served separately from the balancer port.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct AdminCfg {
+    /// The IP address and port the admin API listens on (e.g., "127.0.0.1:9091").
+    #[serde(rename = "address")]
+    pub address: String,
+}
+
+impl AdminCfg {
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        if !is_valid_address(&self.address) {
+            errors.push(
+                "admin.address",
+                format!("\"{}\" is not a valid host:port address", self.address),
+            );
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::config::validation::{ValidationErrors, is_valid_address};
+use serde::{Deserialize, Serialize};
+
+/// Backend-side agent the health monitor asks for load feedback, as in HAProxy's agent-check.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct AgentCfg {
+    /// Port the agent listens on.
+    #[serde(rename = "port")]
+    pub port: u16,
+
+    /// Host the agent runs on; defaults to the host of the backend `address`.
+    #[serde(rename = "host", default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    /// Optional text written to the agent before its reply is read.
+    #[serde(rename = "send", default, skip_serializing_if = "Option::is_none")]
+    pub send: Option<String>,
+
+    /// Time in milliseconds the agent has to reply; defaults to `balancer.health_timeout_ms`.
+    #[serde(
+        rename = "timeout_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout_ms: Option<u64>,
+}
+
+impl AgentCfg {
+    /// `host:port` of the agent for a backend serving on `backend_addr`.
+    pub fn target(&self, backend_addr: &str) -> String {
+        let host = match &self.host {
+            Some(host) => host.as_str(),
+            None => backend_addr
+                .rsplit_once(':')
+                .map_or(backend_addr, |(host, _)| host),
+        };
+        format!("{}:{}", host, self.port)
+    }
+
+    pub fn validate(&self, path: &str, errors: &mut ValidationErrors) {
+        if self.port == 0 {
+            errors.push(format!("{}.port", path), "must be between 1 and 65535");
+        }
+        if let Some(host) = &self.host
+            && !is_valid_address(&format!("{}:{}", host, self.port.max(1)))
+        {
+            errors.push(
+                format!("{}.host", path),
+                format!("\"{}\" is not a valid host", host),
+            );
+        }
+        if self.timeout_ms == Some(0) {
+            errors.push(format!("{}.timeout_ms", path), "must be greater than 0");
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::validation::ValidationErrors;
 use serde::{Deserialize, Serialize};
 
 /// Supported load balancing strategies.
@@ -13,4 +14,82 @@ pub enum AlgorithmType {
     RoundRobin,
     /// Distribution based on predefined server weights.
     WeightedRoundRobin,
+    /// Client affinity by hashing the client IP onto a ring of backends.
+    ConsistentHash,
+    /// Client affinity through a Maglev lookup table of backends.
+    Maglev,
+    /// Consistent hashing that skips backends loaded above `(1 + epsilon)` times the average.
+    BoundedConsistentHash,
+    /// Picks the better of two randomly sampled backends by `p2c_score`.
+    P2c,
+    /// Weighs the latency of real connections, tracked as a Peak-EWMA, by outstanding connections.
+    PeakEwma,
+}
+
+/// What the `p2c` algorithm compares between its two sampled backends.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum P2cScore {
+    /// Fewer active connections wins.
+    #[default]
+    Connections,
+    /// Lower average health-check latency wins.
+    Latency,
+    /// Lower latency multiplied by outstanding connections wins.
+    Combined,
+}
+
+/// Tuning for the algorithms that have parameters; unused ones are ignored.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct AlgorithmOptionsCfg {
+    /// How far above the average `active_conn` a backend may go under `bounded_consistent_hash`.
+    #[serde(
+        rename = "bounded_load_epsilon",
+        default = "default_bounded_load_epsilon"
+    )]
+    pub bounded_load_epsilon: f64,
+
+    /// Score the `p2c` algorithm compares.
+    #[serde(rename = "p2c_score", default)]
+    pub p2c_score: P2cScore,
+
+    /// Time in milliseconds over which `peak_ewma` forgets a latency sample.
+    #[serde(rename = "peak_ewma_decay_ms", default = "default_peak_ewma_decay_ms")]
+    pub peak_ewma_decay_ms: u64,
+}
+
+impl Default for AlgorithmOptionsCfg {
+    fn default() -> Self {
+        Self {
+            bounded_load_epsilon: default_bounded_load_epsilon(),
+            p2c_score: P2cScore::default(),
+            peak_ewma_decay_ms: default_peak_ewma_decay_ms(),
+        }
+    }
+}
+
+fn default_bounded_load_epsilon() -> f64 {
+    0.25
+}
+
+fn default_peak_ewma_decay_ms() -> u64 {
+    10_000
+}
+
+impl AlgorithmOptionsCfg {
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        if !(self.bounded_load_epsilon.is_finite() && self.bounded_load_epsilon > 0.0) {
+            errors.push(
+                "balancer.algorithm_options.bounded_load_epsilon",
+                "must be greater than 0",
+            );
+        }
+        if self.peak_ewma_decay_ms == 0 {
+            errors.push(
+                "balancer.algorithm_options.peak_ewma_decay_ms",
+                "must be greater than 0",
+            );
+        }
+    }
 }
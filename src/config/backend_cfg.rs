@@ -1,3 +1,7 @@
+use crate::config::agent_cfg::AgentCfg;
+use crate::config::health_check_cfg::HealthCheckCfg;
+use crate::config::retry_cfg::RetryOverrideCfg;
+use crate::config::validation::{ValidationErrors, is_valid_address};
 use serde::{Deserialize, Serialize};
 
 /// A specific server destination where traffic is routed.
@@ -13,6 +17,22 @@ pub struct BackendCfg {
     #[serde(rename = "address")]
     pub address: String,
 
+    /// Address probed by health checks instead of `address` (e.g. "10.0.0.1:9100").
+    #[serde(
+        rename = "health_address",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub health_address: Option<String>,
+
+    /// Port probed by health checks instead of the port of `address` or `health_address`.
+    #[serde(
+        rename = "health_port",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub health_port: Option<u16>,
+
     /// Maximum concurrent connections allowed for this backend.
     #[serde(rename = "max_connections")]
     pub max_conn: u64,
@@ -20,4 +40,72 @@ pub struct BackendCfg {
     /// Relative priority/capacity of this backend compared to others.
     #[serde(rename = "weight")]
     pub weight: u64,
-}
\ No newline at end of file
+
+    /// Overrides of the balancer retry delays applied after a connect failure on this backend.
+    #[serde(rename = "retry", default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryOverrideCfg>,
+
+    /// Probe used by the health monitor; a plain TCP connect when unset.
+    #[serde(
+        rename = "health_check",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub health_check: Option<HealthCheckCfg>,
+
+    /// Agent polled every health interval for weight and drain/maintenance requests.
+    #[serde(rename = "agent", default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<AgentCfg>,
+}
+
+impl BackendCfg {
+    pub fn validate(&self, path: &str, errors: &mut ValidationErrors) {
+        if self.id.trim().is_empty() {
+            errors.push(format!("{}.id", path), "must not be empty");
+        }
+        if !is_valid_address(&self.address) {
+            errors.push(
+                format!("{}.address", path),
+                format!("\"{}\" is not a valid host:port address", self.address),
+            );
+        }
+        if let Some(health_address) = &self.health_address
+            && !is_valid_address(health_address)
+        {
+            errors.push(
+                format!("{}.health_address", path),
+                format!("\"{}\" is not a valid host:port address", health_address),
+            );
+        }
+        if self.health_port == Some(0) {
+            errors.push(
+                format!("{}.health_port", path),
+                "must be between 1 and 65535",
+            );
+        }
+        if self.max_conn == 0 {
+            errors.push(
+                format!("{}.max_connections", path),
+                "must be greater than 0",
+            );
+        }
+        if let Some(retry) = &self.retry {
+            retry.validate(&format!("{}.retry", path), errors);
+        }
+        if let Some(health_check) = &self.health_check {
+            health_check.validate(&format!("{}.health_check", path), errors);
+        }
+        if let Some(agent) = &self.agent {
+            agent.validate(&format!("{}.agent", path), errors);
+        }
+    }
+
+    /// Address health checks probe, built from `health_address` and `health_port` over `address`.
+    pub fn health_target(&self) -> String {
+        let addr = self.health_address.as_deref().unwrap_or(&self.address);
+        match (self.health_port, addr.rsplit_once(':')) {
+            (Some(port), Some((host, _))) => format!("{}:{}", host, port),
+            _ => addr.to_string(),
+        }
+    }
+}
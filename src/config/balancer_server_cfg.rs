@@ -1,4 +1,6 @@
-use crate::config::algorithm_cfg::AlgorithmType;
+use crate::config::algorithm_cfg::{AlgorithmOptionsCfg, AlgorithmType};
+use crate::config::retry_cfg::RetryCfg;
+use crate::config::validation::{ValidationErrors, is_ratio};
 use serde::{Deserialize, Serialize};
 
 /// Settings defining how the balancer operates.
@@ -9,11 +11,100 @@ pub struct BalancerServerCfg {
     #[serde[rename = "algorithm"]]
     pub algorithm: AlgorithmType,
 
+    /// Parameters of the selected algorithm.
+    #[serde(rename = "algorithm_options", default)]
+    pub algorithm_options: AlgorithmOptionsCfg,
+
     /// Frequency of health checks in milliseconds.
     #[serde(rename = "check_interval_ms")]
     pub check_interval_ms: u64,
 
+    /// Time in milliseconds a single health probe may take before it counts as failed.
+    #[serde(rename = "health_timeout_ms", default = "default_health_timeout_ms")]
+    pub health_timeout_ms: u64,
+
+    /// Maximum number of health probes running at the same time.
+    #[serde(rename = "health_concurrency", default = "default_health_concurrency")]
+    pub health_concurrency: usize,
+
+    /// The ratio (0.0 - 1.0) of `check_interval_ms` by which each probe is randomly delayed.
+    #[serde(rename = "health_jitter", default)]
+    pub health_jitter: f64,
+
+    /// Interval in milliseconds for a summary event of backend states; unset disables it.
+    #[serde(
+        rename = "health_summary_interval_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub health_summary_interval_ms: Option<u64>,
+
     /// The network port the load balancer listens on.
     #[serde(rename = "port")]
-    pub port: i16,
+    pub port: u16,
+
+    /// Backoff policy for backend selection and upstream connect failures.
+    #[serde(rename = "retry", default)]
+    pub retry: RetryCfg,
+
+    /// Time in milliseconds in-flight connections get to finish on shutdown before being closed.
+    #[serde(rename = "drain_timeout_ms", default = "default_drain_timeout_ms")]
+    pub drain_timeout_ms: u64,
+
+    /// Poll interval in milliseconds for reloading the config file when it changes; unset disables watching.
+    #[serde(
+        rename = "watch_config_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub watch_config_ms: Option<u64>,
+}
+
+fn default_health_timeout_ms() -> u64 {
+    2_000
+}
+
+fn default_health_concurrency() -> usize {
+    16
+}
+
+fn default_drain_timeout_ms() -> u64 {
+    10_000
+}
+
+impl BalancerServerCfg {
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        if self.port == 0 {
+            errors.push("balancer.port", "must be between 1 and 65535");
+        }
+        if self.check_interval_ms == 0 {
+            errors.push("balancer.check_interval_ms", "must be greater than 0");
+        }
+        if self.health_timeout_ms == 0 {
+            errors.push("balancer.health_timeout_ms", "must be greater than 0");
+        }
+        if self.health_concurrency == 0 {
+            errors.push("balancer.health_concurrency", "must be greater than 0");
+        }
+        if !is_ratio(self.health_jitter) {
+            errors.push(
+                "balancer.health_jitter",
+                format!(
+                    "{} is outside the ratio range 0.0 - 1.0",
+                    self.health_jitter
+                ),
+            );
+        }
+        if self.health_summary_interval_ms == Some(0) {
+            errors.push(
+                "balancer.health_summary_interval_ms",
+                "must be greater than 0",
+            );
+        }
+        if self.watch_config_ms == Some(0) {
+            errors.push("balancer.watch_config_ms", "must be greater than 0");
+        }
+        self.algorithm_options.validate(errors);
+        self.retry.validate("balancer.retry", errors);
+    }
 }
@@ -0,0 +1,258 @@
+use crate::config::validation::ValidationErrors;
+use serde::{Deserialize, Serialize};
+
+/// How a backend is probed by the health monitor.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HealthCheckCfg {
+    /// A plain TCP connect to the backend address.
+    #[default]
+    Tcp,
+    /// An HTTP/1.1 request whose response status and body decide the outcome.
+    Http(HttpCheckCfg),
+    /// A raw payload written over TCP and a reply expected back.
+    SendExpect(SendExpectCheckCfg),
+    /// An external command whose exit status decides the outcome.
+    Exec(ExecCheckCfg),
+    /// A call to the standard `grpc.health.v1.Health/Check` service.
+    Grpc(GrpcCheckCfg),
+}
+
+/// Request sent by an HTTP health check and the response it expects.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct HttpCheckCfg {
+    /// Request path including any query string.
+    #[serde(rename = "path", default = "default_path")]
+    pub path: String,
+
+    /// Request method.
+    #[serde(rename = "method", default = "default_method")]
+    pub method: String,
+
+    /// `Host` header value; defaults to the host part of the probed address.
+    #[serde(rename = "host", default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    /// Status codes or inclusive ranges (e.g. `204`, `"200-399"`) counted as passing.
+    #[serde(rename = "expected_status", default = "default_expected_status")]
+    pub expected_status: Vec<StatusRange>,
+
+    /// Substring the response body must contain.
+    #[serde(
+        rename = "body_contains",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub body_contains: Option<String>,
+
+    /// Regular expression the response body must match.
+    #[serde(
+        rename = "body_regex",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub body_regex: Option<String>,
+
+    /// Time in milliseconds the whole exchange may take; defaults to `balancer.health_timeout_ms`.
+    #[serde(
+        rename = "timeout_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Payload written by a send/expect health check and the reply it waits for.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct SendExpectCheckCfg {
+    /// Bytes written after connecting; empty waits for the server to speak first.
+    #[serde(rename = "send", default)]
+    pub send: String,
+
+    /// Text the reply must contain; empty passes on any reply.
+    #[serde(rename = "expect")]
+    pub expect: String,
+
+    /// Time in milliseconds the whole exchange may take; defaults to `balancer.health_timeout_ms`.
+    #[serde(
+        rename = "timeout_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout_ms: Option<u64>,
+}
+
+/// External command run by an exec health check.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct ExecCheckCfg {
+    /// Program to run, looked up on `PATH` when not a path.
+    #[serde(rename = "command")]
+    pub command: String,
+
+    /// Arguments passed to the program.
+    #[serde(rename = "args", default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// Time in milliseconds the command may run before it is killed; defaults to `balancer.health_timeout_ms`.
+    #[serde(
+        rename = "timeout_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Service queried by a gRPC health check.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct GrpcCheckCfg {
+    /// Service name sent in the request; empty asks about the server as a whole.
+    #[serde(rename = "service", default)]
+    pub service: String,
+
+    /// Time in milliseconds connecting and the call may take; defaults to `balancer.health_timeout_ms`.
+    #[serde(
+        rename = "timeout_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout_ms: Option<u64>,
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_expected_status() -> Vec<StatusRange> {
+    vec![StatusRange {
+        start: 200,
+        end: 399,
+    }]
+}
+
+/// Inclusive range of HTTP status codes, written as a single code or `"start-end"`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "StatusRangeRepr", into = "StatusRangeRepr")]
+pub struct StatusRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl StatusRange {
+    pub fn contains(&self, status: u16) -> bool {
+        (self.start..=self.end).contains(&status)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StatusRangeRepr {
+    Code(u16),
+    Range(String),
+}
+
+impl TryFrom<StatusRangeRepr> for StatusRange {
+    type Error = String;
+
+    fn try_from(repr: StatusRangeRepr) -> Result<Self, Self::Error> {
+        match repr {
+            StatusRangeRepr::Code(code) => Ok(Self {
+                start: code,
+                end: code,
+            }),
+            StatusRangeRepr::Range(range) => {
+                let parse = |s: &str| {
+                    s.trim()
+                        .parse::<u16>()
+                        .map_err(|_| format!("\"{}\" is not a status code or range", range))
+                };
+                match range.split_once('-') {
+                    Some((start, end)) => Ok(Self {
+                        start: parse(start)?,
+                        end: parse(end)?,
+                    }),
+                    None => {
+                        let code = parse(&range)?;
+                        Ok(Self {
+                            start: code,
+                            end: code,
+                        })
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<StatusRange> for StatusRangeRepr {
+    fn from(range: StatusRange) -> Self {
+        if range.start == range.end {
+            StatusRangeRepr::Code(range.start)
+        } else {
+            StatusRangeRepr::Range(format!("{}-{}", range.start, range.end))
+        }
+    }
+}
+
+impl HealthCheckCfg {
+    pub fn validate(&self, path: &str, errors: &mut ValidationErrors) {
+        match self {
+            HealthCheckCfg::Tcp => {}
+            HealthCheckCfg::Http(http) => http.validate(path, errors),
+            HealthCheckCfg::SendExpect(send_expect) => {
+                validate_timeout(send_expect.timeout_ms, path, errors)
+            }
+            HealthCheckCfg::Exec(exec) => {
+                if exec.command.trim().is_empty() {
+                    errors.push(format!("{}.command", path), "must not be empty");
+                }
+                validate_timeout(exec.timeout_ms, path, errors);
+            }
+            HealthCheckCfg::Grpc(grpc) => validate_timeout(grpc.timeout_ms, path, errors),
+        }
+    }
+}
+
+impl HttpCheckCfg {
+    pub fn validate(&self, path: &str, errors: &mut ValidationErrors) {
+        if !self.path.starts_with('/') {
+            errors.push(format!("{}.path", path), "must start with \"/\"");
+        }
+        if self.method.is_empty() || !self.method.chars().all(|c| c.is_ascii_uppercase()) {
+            errors.push(
+                format!("{}.method", path),
+                format!("\"{}\" is not a valid HTTP method", self.method),
+            );
+        }
+        if self.expected_status.is_empty() {
+            errors.push(format!("{}.expected_status", path), "must not be empty");
+        }
+        for (i, range) in self.expected_status.iter().enumerate() {
+            if range.start < 100 || range.end > 599 || range.start > range.end {
+                errors.push(
+                    format!("{}.expected_status[{}]", path, i),
+                    format!("{}-{} is not a valid status range", range.start, range.end),
+                );
+            }
+        }
+        if let Some(pattern) = &self.body_regex
+            && let Err(e) = regex::Regex::new(pattern)
+        {
+            errors.push(format!("{}.body_regex", path), e);
+        }
+        validate_timeout(self.timeout_ms, path, errors);
+    }
+}
+
+fn validate_timeout(timeout_ms: Option<u64>, path: &str, errors: &mut ValidationErrors) {
+    if timeout_ms == Some(0) {
+        errors.push(format!("{}.timeout_ms", path), "must be greater than 0");
+    }
+}
@@ -1,7 +1,11 @@
+use crate::config::admin_cfg::AdminCfg;
 use crate::config::backend_cfg::BackendCfg;
 use crate::config::balancer_server_cfg::BalancerServerCfg;
 use crate::config::thresholds_cfg::ThresholdsCfg;
+use crate::config::validation::ValidationErrors;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 use tokio::fs;
 
 /// Root configuration for the load balancer system.
@@ -21,13 +25,82 @@ pub struct LoadBalancerCfg {
     /// Safety limits and recovery parameters for traffic management.
     #[serde(rename = "thresholds")]
     pub thresholds_cfg: ThresholdsCfg,
+
+    /// Optional admin API; disabled when the section is absent.
+    #[serde(rename = "admin", default, skip_serializing_if = "Option::is_none")]
+    pub admin_cfg: Option<AdminCfg>,
 }
 
 impl LoadBalancerCfg {
     /// Loads configuration from the default config file
     pub async fn load() -> anyhow::Result<LoadBalancerCfg> {
-        let raw = fs::read_to_string("config.yaml").await?;
-        let cfg: LoadBalancerCfg = serde_yaml::from_str(&raw.as_str())?;
+        Self::load_from("config.yaml").await
+    }
+
+    /// Loads configuration from the given config file
+    pub async fn load_from(path: impl AsRef<Path>) -> anyhow::Result<LoadBalancerCfg> {
+        let raw = fs::read_to_string(path).await?;
+        let cfg: LoadBalancerCfg = serde_yaml::from_str(raw.as_str())?;
         Ok(cfg)
     }
+
+    /// Checks the values serde cannot, reporting every problem with the YAML path it was found at.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut errors = ValidationErrors::default();
+
+        self.balancer_cfg.validate(&mut errors);
+        self.thresholds_cfg.validate(&mut errors);
+        if let Some(admin_cfg) = &self.admin_cfg {
+            admin_cfg.validate(&mut errors);
+        }
+
+        if self.backends.is_empty() {
+            errors.push("backends", "at least one backend is required");
+        }
+
+        let mut ids: HashMap<&str, usize> = HashMap::new();
+        let mut addresses: HashMap<&str, usize> = HashMap::new();
+        for (idx, backend) in self.backends.iter().enumerate() {
+            let path = format!("backends[{}]", idx);
+            backend.validate(&path, &mut errors);
+
+            let first = *ids.entry(&backend.id).or_insert(idx);
+            if first != idx {
+                errors.push(
+                    format!("{}.id", path),
+                    format!(
+                        "duplicate id \"{}\", already used by backends[{}]",
+                        backend.id, first
+                    ),
+                );
+            }
+            let first = *addresses.entry(&backend.address).or_insert(idx);
+            if first != idx {
+                errors.push(
+                    format!("{}.address", path),
+                    format!(
+                        "duplicate address \"{}\", already used by backends[{}]",
+                        backend.address, first
+                    ),
+                );
+            }
+
+            // Recovery could never land on a weight smaller than a single step.
+            if self.thresholds_cfg.recovery_step > backend.weight {
+                errors.push(
+                    format!("{}.weight", path),
+                    format!(
+                        "{} is smaller than thresholds.recovery_step ({})",
+                        backend.weight, self.thresholds_cfg.recovery_step
+                    ),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.into())
+        }
+    }
 }
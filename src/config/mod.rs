@@ -1,5 +1,10 @@
+pub mod admin_cfg;
+pub mod agent_cfg;
 pub mod algorithm_cfg;
 pub mod backend_cfg;
 pub mod balancer_server_cfg;
+pub mod health_check_cfg;
 pub mod load_balancer_cfg;
+pub mod retry_cfg;
 pub mod thresholds_cfg;
+pub mod validation;
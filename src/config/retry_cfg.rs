@@ -0,0 +1,136 @@
+use crate::config::validation::{ValidationErrors, is_ratio};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Backoff policy applied while waiting for an eligible backend and when connecting upstream fails.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+#[allow(dead_code)]
+pub struct RetryCfg {
+    /// Total attempts per client connection, including the first one.
+    #[serde(rename = "max_attempts")]
+    pub max_attempts: u64,
+
+    /// Delay before the first retry in milliseconds.
+    #[serde(rename = "initial_delay_ms")]
+    pub initial_delay_ms: u64,
+
+    /// Multiplier applied to the delay after every retry (1.0 keeps it constant).
+    #[serde(rename = "backoff_factor")]
+    pub backoff_factor: f64,
+
+    /// Upper bound for a single delay in milliseconds.
+    #[serde(rename = "max_delay_ms")]
+    pub max_delay_ms: u64,
+
+    /// The ratio (0.0 - 1.0) by which each delay is randomly shortened.
+    #[serde(rename = "jitter")]
+    pub jitter: f64,
+
+    /// Total time budget in milliseconds for all attempts of one client connection (0 disables it).
+    #[serde(rename = "deadline_ms")]
+    pub deadline_ms: u64,
+}
+
+impl Default for RetryCfg {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay_ms: 100,
+            backoff_factor: 1.0,
+            max_delay_ms: 100,
+            jitter: 0.0,
+            deadline_ms: 1000,
+        }
+    }
+}
+
+/// Per-backend overrides of [`RetryCfg`]; unset fields fall back to the balancer-wide policy.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[allow(dead_code)]
+pub struct RetryOverrideCfg {
+    #[serde(rename = "initial_delay_ms", default)]
+    pub initial_delay_ms: Option<u64>,
+
+    #[serde(rename = "backoff_factor", default)]
+    pub backoff_factor: Option<f64>,
+
+    #[serde(rename = "max_delay_ms", default)]
+    pub max_delay_ms: Option<u64>,
+
+    #[serde(rename = "jitter", default)]
+    pub jitter: Option<f64>,
+}
+
+impl RetryCfg {
+    /// Delay to wait before the given retry (1-based), grown by `backoff_factor`,
+    /// capped at `max_delay_ms` and shortened by up to `jitter` of its length.
+    pub fn delay(&self, retry: u64) -> Duration {
+        let exp = self.backoff_factor.powi(retry.saturating_sub(1) as i32);
+        let delay = (self.initial_delay_ms as f64 * exp).min(self.max_delay_ms as f64);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let delay = if jitter > 0.0 {
+            delay * (1.0 - rand::random_range(0.0..jitter))
+        } else {
+            delay
+        };
+        Duration::from_millis(delay as u64)
+    }
+
+    /// Returns this policy with the delay settings replaced by any override present.
+    pub fn with_override(&self, over: Option<&RetryOverrideCfg>) -> RetryCfg {
+        let Some(over) = over else {
+            return self.clone();
+        };
+        RetryCfg {
+            max_attempts: self.max_attempts,
+            initial_delay_ms: over.initial_delay_ms.unwrap_or(self.initial_delay_ms),
+            backoff_factor: over.backoff_factor.unwrap_or(self.backoff_factor),
+            max_delay_ms: over.max_delay_ms.unwrap_or(self.max_delay_ms),
+            jitter: over.jitter.unwrap_or(self.jitter),
+            deadline_ms: self.deadline_ms,
+        }
+    }
+
+    pub fn validate(&self, path: &str, errors: &mut ValidationErrors) {
+        if self.max_attempts == 0 {
+            errors.push(format!("{}.max_attempts", path), "must be greater than 0");
+        }
+        if self.max_delay_ms < self.initial_delay_ms {
+            errors.push(
+                format!("{}.max_delay_ms", path),
+                format!(
+                    "{} is smaller than initial_delay_ms ({})",
+                    self.max_delay_ms, self.initial_delay_ms
+                ),
+            );
+        }
+        validate_backoff(path, self.backoff_factor, self.jitter, errors);
+    }
+}
+
+impl RetryOverrideCfg {
+    pub fn validate(&self, path: &str, errors: &mut ValidationErrors) {
+        validate_backoff(
+            path,
+            self.backoff_factor.unwrap_or(1.0),
+            self.jitter.unwrap_or(0.0),
+            errors,
+        );
+    }
+}
+
+fn validate_backoff(path: &str, backoff_factor: f64, jitter: f64, errors: &mut ValidationErrors) {
+    if backoff_factor.is_nan() || backoff_factor < 1.0 {
+        errors.push(
+            format!("{}.backoff_factor", path),
+            format!("{} must be at least 1.0", backoff_factor),
+        );
+    }
+    if !is_ratio(jitter) {
+        errors.push(
+            format!("{}.jitter", path),
+            format!("{} is outside the ratio range 0.0 - 1.0", jitter),
+        );
+    }
+}
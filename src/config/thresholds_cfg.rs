@@ -1,3 +1,4 @@
+use crate::config::validation::{ValidationErrors, is_ratio};
 use serde::{Deserialize, Serialize};
 
 /// Rules for circuit breaking and health monitoring.
@@ -16,4 +17,120 @@ pub struct ThresholdsCfg {
     /// The increment by which weight is restored during server recovery.
     #[serde(rename = "recovery_step")]
     pub recovery_step: u64,
+
+    /// Sliding window in milliseconds over which `error_rate_limit` is evaluated.
+    #[serde(rename = "error_window_ms", default = "default_error_window_ms")]
+    pub error_window_ms: u64,
+
+    /// Minimum connections in the window before a backend can be ejected for its error rate.
+    #[serde(rename = "error_min_samples", default = "default_error_min_samples")]
+    pub error_min_samples: u64,
+
+    /// Time in milliseconds an open circuit breaker waits before a passing health check may half-open it.
+    #[serde(rename = "breaker_open_ms", default = "default_breaker_open_ms")]
+    pub breaker_open_ms: u64,
+
+    /// Concurrent trial connections a half-open breaker admits, and successes needed to close it.
+    #[serde(
+        rename = "breaker_half_open_trials",
+        default = "default_breaker_half_open_trials"
+    )]
+    pub breaker_half_open_trials: u64,
+
+    /// Consecutive passing health checks needed before a down backend is considered recovered.
+    #[serde(rename = "rise", default = "default_rise")]
+    pub rise: u64,
+
+    /// Consecutive failing health checks needed before a backend is marked down.
+    #[serde(rename = "fall", default = "default_fall")]
+    pub fall: u64,
+
+    /// Time in milliseconds over which a backend returning to rotation ramps up to its full share (0 disables it).
+    #[serde(rename = "slow_start_ms", default)]
+    pub slow_start_ms: u64,
+
+    /// How the share grows during `slow_start_ms`.
+    #[serde(rename = "slow_start_curve", default)]
+    pub slow_start_curve: SlowStartCurve,
+}
+
+/// Shape of the slow-start ramp over its window.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowStartCurve {
+    /// The share grows evenly from 0 to 1.
+    #[default]
+    Linear,
+    /// The share doubles at even steps, staying small for longer and catching up at the end.
+    Exponential,
+}
+
+impl SlowStartCurve {
+    /// Share of traffic for a backend `progress` (0.0 - 1.0) of the way through its window.
+    pub fn factor(&self, progress: f64) -> f64 {
+        let progress = progress.clamp(0.0, 1.0);
+        match self {
+            SlowStartCurve::Linear => progress,
+            SlowStartCurve::Exponential => (2f64.powf(10.0 * progress) - 1.0) / 1023.0,
+        }
+    }
+}
+
+fn default_error_window_ms() -> u64 {
+    10_000
+}
+
+fn default_error_min_samples() -> u64 {
+    10
+}
+
+fn default_breaker_open_ms() -> u64 {
+    5_000
+}
+
+fn default_breaker_half_open_trials() -> u64 {
+    3
+}
+
+fn default_rise() -> u64 {
+    2
+}
+
+fn default_fall() -> u64 {
+    3
+}
+
+impl ThresholdsCfg {
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        if self.latency_critical_ms == 0 {
+            errors.push("thresholds.latency_critical_ms", "must be greater than 0");
+        }
+        if !is_ratio(self.error_rate_limit as f64) {
+            errors.push(
+                "thresholds.error_rate_limit",
+                format!(
+                    "{} is outside the ratio range 0.0 - 1.0",
+                    self.error_rate_limit
+                ),
+            );
+        }
+        if self.error_window_ms == 0 {
+            errors.push("thresholds.error_window_ms", "must be greater than 0");
+        }
+        if self.breaker_half_open_trials == 0 {
+            errors.push(
+                "thresholds.breaker_half_open_trials",
+                "must be greater than 0",
+            );
+        }
+        if self.rise == 0 {
+            errors.push("thresholds.rise", "must be greater than 0");
+        }
+        if self.fall == 0 {
+            errors.push("thresholds.fall", "must be greater than 0");
+        }
+        if self.recovery_step == 0 {
+            errors.push("thresholds.recovery_step", "must be greater than 0");
+        }
+    }
 }
@@ -0,0 +1,54 @@
+use std::fmt;
+use std::net::SocketAddr;
+
+/// Problems found while validating a config, each prefixed with the YAML path it refers to.
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    errors: Vec<String>,
+}
+
+impl ValidationErrors {
+    pub fn push(&mut self, path: impl fmt::Display, msg: impl fmt::Display) {
+        self.errors.push(format!("{}: {}", path, msg));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn messages(&self) -> &[String] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} configuration error(s)", self.errors.len())?;
+        for err in &self.errors {
+            write!(f, "\n  - {}", err)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Accepts `ip:port` socket addresses as well as `host:port` names resolved at connect time.
+pub fn is_valid_address(addr: &str) -> bool {
+    if addr.parse::<SocketAddr>().is_ok() {
+        return true;
+    }
+    match addr.rsplit_once(':') {
+        Some((host, port)) => {
+            !host.is_empty()
+                && !host.contains(|c: char| c.is_whitespace() || c == '/' || c == ':')
+                && port.parse::<u16>().is_ok_and(|p| p != 0)
+        }
+        None => false,
+    }
+}
+
+/// Checks a ratio lies within 0.0 - 1.0.
+pub fn is_ratio(value: f64) -> bool {
+    (0.0..=1.0).contains(&value)
+}
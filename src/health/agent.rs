@@ -0,0 +1,70 @@
+use anyhow::bail;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+/// Agent replies longer than this are cut off.
+const MAX_LINE_BYTES: u64 = 1024;
+
+/// What a backend agent asked for in its reply line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentCommand {
+    /// Return to rotation after an agent-requested drain or maintenance.
+    Up,
+    /// Stop taking new connections, let in-flight ones finish.
+    Drain,
+    /// Leave rotation and stop health checking.
+    Maint,
+}
+
+/// A parsed agent reply; fields the agent did not mention are `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AgentReport {
+    /// Share of `base_weight` the backend wants, in percent.
+    pub weight_pct: Option<u64>,
+    pub command: Option<AgentCommand>,
+}
+
+impl AgentReport {
+    /// Parses a line such as `weight=40%`, `75% up`, `drain` or `maint`.
+    ///
+    /// Tokens may be separated by spaces or commas and unknown tokens are ignored, so agents can
+    /// add information for other tools.
+    pub fn parse(line: &str) -> Self {
+        let mut report = Self::default();
+        for token in line.split([' ', ',', '\t']).filter(|t| !t.is_empty()) {
+            let token = token.to_ascii_lowercase();
+            let value = token.strip_prefix("weight=").unwrap_or(&token);
+
+            if let Some(pct) = value.strip_suffix('%') {
+                if let Ok(pct) = pct.parse::<u64>() {
+                    report.weight_pct = Some(pct.min(100));
+                }
+                continue;
+            }
+            report.command = match value {
+                "up" | "ready" => Some(AgentCommand::Up),
+                "drain" => Some(AgentCommand::Drain),
+                "maint" => Some(AgentCommand::Maint),
+                _ => report.command,
+            };
+        }
+        report
+    }
+}
+
+/// Connects to the agent at `addr`, optionally writes `send`, and parses the first line it replies with.
+pub async fn query(addr: &str, send: Option<&str>) -> anyhow::Result<AgentReport> {
+    let mut stream = TcpStream::connect(addr).await?;
+    if let Some(send) = send {
+        stream.write_all(send.as_bytes()).await?;
+    }
+
+    let mut line = String::new();
+    let n = BufReader::new(stream.take(MAX_LINE_BYTES))
+        .read_line(&mut line)
+        .await?;
+    if n == 0 {
+        bail!("agent closed the connection without a reply");
+    }
+    Ok(AgentReport::parse(line.trim()))
+}
@@ -0,0 +1,51 @@
+use crate::config::health_check_cfg::ExecCheckCfg;
+use crate::health::traits::health_check::HealthCheck;
+use anyhow::bail;
+use async_trait::async_trait;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::Instant;
+
+/// Runs an external command and passes when it exits with status 0.
+///
+/// The probed address is passed in `LB_BACKEND_ADDR`, `LB_BACKEND_HOST` and `LB_BACKEND_PORT`.
+/// The command is killed if the check times out. The latency is the command's run time.
+pub struct ExecCheck {
+    cfg: ExecCheckCfg,
+}
+
+impl ExecCheck {
+    pub fn new(cfg: ExecCheckCfg) -> Self {
+        Self { cfg }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for ExecCheck {
+    async fn check(&self, addr: &str) -> anyhow::Result<u64> {
+        let (host, port) = addr.rsplit_once(':').unwrap_or((addr, ""));
+        let start = Instant::now();
+
+        let status = Command::new(&self.cfg.command)
+            .args(&self.cfg.args)
+            .env("LB_BACKEND_ADDR", addr)
+            .env("LB_BACKEND_HOST", host)
+            .env("LB_BACKEND_PORT", port)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .status()
+            .await?;
+
+        if !status.success() {
+            bail!("{} exited with {}", self.cfg.command, status);
+        }
+        Ok(start.elapsed().as_millis() as u64)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.cfg.timeout_ms.map(Duration::from_millis)
+    }
+}
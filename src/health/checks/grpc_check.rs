@@ -0,0 +1,54 @@
+use crate::config::health_check_cfg::GrpcCheckCfg;
+use crate::health::traits::health_check::HealthCheck;
+use anyhow::bail;
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::time::Instant;
+use tonic::transport::Endpoint;
+use tonic_health::pb::HealthCheckRequest;
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_client::HealthClient;
+
+/// Calls `grpc.health.v1.Health/Check` over plaintext HTTP/2 and passes when the reply is `SERVING`.
+///
+/// `NOT_SERVING`, `UNKNOWN` and `SERVICE_UNKNOWN` all count as failures, so they take the backend
+/// down through the usual `fall` streak. The latency covers connecting and the call.
+pub struct GrpcCheck {
+    cfg: GrpcCheckCfg,
+}
+
+impl GrpcCheck {
+    pub fn new(cfg: GrpcCheckCfg) -> Self {
+        Self { cfg }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for GrpcCheck {
+    async fn check(&self, addr: &str) -> anyhow::Result<u64> {
+        let start = Instant::now();
+        let channel = Endpoint::from_shared(format!("http://{}", addr))?
+            .connect()
+            .await?;
+
+        let response = HealthClient::new(channel)
+            .check(HealthCheckRequest {
+                service: self.cfg.service.clone(),
+            })
+            .await?
+            .into_inner();
+
+        match response.status() {
+            ServingStatus::Serving => Ok(start.elapsed().as_millis() as u64),
+            status => bail!(
+                "service \"{}\" is {}",
+                self.cfg.service,
+                status.as_str_name()
+            ),
+        }
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.cfg.timeout_ms.map(Duration::from_millis)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::config::health_check_cfg::HttpCheckCfg;
+use crate::health::traits::health_check::HealthCheck;
+use anyhow::{Context, bail};
+use async_trait::async_trait;
+use regex::Regex;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+
+/// Responses larger than this are cut off before the body is matched.
+const MAX_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// Sends the configured request and passes when the status and body match what is expected.
+///
+/// The latency is the time to the first response byte. The request asks for `Connection: close`,
+/// so the body is read until the backend closes.
+pub struct HttpCheck {
+    cfg: HttpCheckCfg,
+    body_regex: Option<Result<Regex, regex::Error>>,
+}
+
+impl HttpCheck {
+    pub fn new(cfg: HttpCheckCfg) -> Self {
+        let body_regex = cfg.body_regex.as_deref().map(Regex::new);
+        Self { cfg, body_regex }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for HttpCheck {
+    async fn check(&self, addr: &str) -> anyhow::Result<u64> {
+        let cfg = &self.cfg;
+        let start = Instant::now();
+        let mut stream = TcpStream::connect(addr).await?;
+
+        let host = match &cfg.host {
+            Some(host) => host.as_str(),
+            None => addr.rsplit_once(':').map_or(addr, |(host, _)| host),
+        };
+        let request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: monad-load-balancer\r\nAccept: */*\r\nConnection: close\r\n\r\n",
+            cfg.method, cfg.path, host
+        );
+        stream.write_all(request.as_bytes()).await?;
+
+        let mut buf = Vec::with_capacity(1024);
+        let mut chunk = [0u8; 4096];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            bail!("connection closed before a response");
+        }
+        let latency = start.elapsed().as_millis() as u64;
+        buf.extend_from_slice(&chunk[..n]);
+
+        let needs_body = cfg.body_contains.is_some() || cfg.body_regex.is_some();
+        loop {
+            let head_done = buf.windows(4).any(|w| w == b"\r\n\r\n");
+            if (head_done && !needs_body) || buf.len() >= MAX_RESPONSE_BYTES {
+                break;
+            }
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+
+        let response = String::from_utf8_lossy(&buf);
+        let status = parse_status(&response).context("malformed HTTP status line")?;
+        if !cfg.expected_status.iter().any(|r| r.contains(status)) {
+            bail!("unexpected status {}", status);
+        }
+
+        let body = response.split_once("\r\n\r\n").map_or("", |(_, body)| body);
+        if let Some(needle) = &cfg.body_contains
+            && !body.contains(needle.as_str())
+        {
+            bail!("body does not contain \"{}\"", needle);
+        }
+        match &self.body_regex {
+            Some(Ok(regex)) if !regex.is_match(body) => {
+                bail!("body does not match /{}/", regex.as_str())
+            }
+            Some(Err(e)) => bail!("invalid body_regex: {}", e),
+            _ => {}
+        }
+
+        Ok(latency)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.cfg.timeout_ms.map(Duration::from_millis)
+    }
+}
+
+fn parse_status(response: &str) -> Option<u16> {
+    let line = response.lines().next()?;
+    let mut parts = line.split_whitespace();
+    if !parts.next()?.starts_with("HTTP/") {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
@@ -0,0 +1,5 @@
+pub mod exec_check;
+pub mod grpc_check;
+pub mod http_check;
+pub mod send_expect_check;
+pub mod tcp_check;
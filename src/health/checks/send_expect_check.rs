@@ -0,0 +1,65 @@
+use crate::config::health_check_cfg::SendExpectCheckCfg;
+use crate::health::traits::health_check::HealthCheck;
+use anyhow::bail;
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+
+/// Replies longer than this are cut off before `expect` is searched.
+const MAX_REPLY_BYTES: usize = 16 * 1024;
+
+/// Writes a raw payload and passes once the reply contains the expected text, e.g. Redis
+/// `PING` answered with `+PONG`. With an empty payload it just waits for the server's banner,
+/// and with an empty `expect` any reply passes.
+///
+/// The latency is the time to the first reply byte.
+pub struct SendExpectCheck {
+    cfg: SendExpectCheckCfg,
+}
+
+impl SendExpectCheck {
+    pub fn new(cfg: SendExpectCheckCfg) -> Self {
+        Self { cfg }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for SendExpectCheck {
+    async fn check(&self, addr: &str) -> anyhow::Result<u64> {
+        let start = Instant::now();
+        let mut stream = TcpStream::connect(addr).await?;
+        if !self.cfg.send.is_empty() {
+            stream.write_all(self.cfg.send.as_bytes()).await?;
+        }
+
+        let expect = self.cfg.expect.as_bytes();
+        let mut buf = Vec::with_capacity(256);
+        let mut chunk = [0u8; 1024];
+        let mut latency = None;
+        loop {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                bail!(
+                    "connection closed before \"{}\" was received",
+                    self.cfg.expect
+                );
+            }
+            latency.get_or_insert_with(|| start.elapsed().as_millis() as u64);
+            buf.extend_from_slice(&chunk[..n]);
+
+            // `windows(0)` panics, so an empty `expect` is matched by the first byte received.
+            if expect.is_empty() || buf.windows(expect.len()).any(|w| w == expect) {
+                return Ok(latency.unwrap_or_default());
+            }
+            if buf.len() >= MAX_REPLY_BYTES {
+                bail!("reply does not contain \"{}\"", self.cfg.expect);
+            }
+        }
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.cfg.timeout_ms.map(Duration::from_millis)
+    }
+}
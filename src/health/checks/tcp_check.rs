@@ -0,0 +1,28 @@
+use crate::health::traits::health_check::HealthCheck;
+use async_trait::async_trait;
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+
+/// Passes when a TCP connection to the backend can be established.
+pub struct TcpCheck;
+
+impl TcpCheck {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TcpCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HealthCheck for TcpCheck {
+    async fn check(&self, addr: &str) -> anyhow::Result<u64> {
+        let start = Instant::now();
+        let _stream = TcpStream::connect(addr).await?;
+        Ok(start.elapsed().as_millis() as u64)
+    }
+}
@@ -0,0 +1,22 @@
+use crate::config::health_check_cfg::HealthCheckCfg;
+use crate::health::checks::exec_check::ExecCheck;
+use crate::health::checks::grpc_check::GrpcCheck;
+use crate::health::checks::http_check::HttpCheck;
+use crate::health::checks::send_expect_check::SendExpectCheck;
+use crate::health::checks::tcp_check::TcpCheck;
+use crate::health::traits::health_check::HealthCheck;
+use std::sync::Arc;
+
+pub struct Check;
+
+impl Check {
+    pub fn select(cfg: HealthCheckCfg) -> Arc<dyn HealthCheck> {
+        match cfg {
+            HealthCheckCfg::Tcp => Arc::new(TcpCheck::new()),
+            HealthCheckCfg::Http(cfg) => Arc::new(HttpCheck::new(cfg)),
+            HealthCheckCfg::SendExpect(cfg) => Arc::new(SendExpectCheck::new(cfg)),
+            HealthCheckCfg::Exec(cfg) => Arc::new(ExecCheck::new(cfg)),
+            HealthCheckCfg::Grpc(cfg) => Arc::new(GrpcCheck::new(cfg)),
+        }
+    }
+}
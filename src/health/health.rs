@@ -1,13 +1,15 @@
 use crate::config::balancer_server_cfg::BalancerServerCfg;
 use crate::config::thresholds_cfg::ThresholdsCfg;
+use crate::health::agent::{self, AgentCommand};
 use crate::logging::events::exporter_event::ExporterEvent;
 use crate::state::backend::Backend;
+use crate::state::backend_state::BackendState;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
-use tokio::net::TcpStream;
-use tokio::sync::RwLock;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::{RwLock, Semaphore};
+use tokio::task::JoinSet;
 use tokio::time::{Instant, sleep, timeout};
 
 pub struct Health {
@@ -30,97 +32,282 @@ impl Health {
             health_tx,
         }
     }
-    pub async fn monitor(&self) -> anyhow::Result<()> {
+    pub async fn monitor(self: Arc<Self>) -> anyhow::Result<()> {
+        let permits = Arc::new(Semaphore::new(self.balancer.health_concurrency));
+        let mut last_summary = Instant::now();
         loop {
             let backends = {
                 let rg = self.backends.read().await;
                 rg.clone()
             };
+
+            // Probes run concurrently so one slow backend cannot stretch the whole cycle.
+            let mut probes = JoinSet::new();
             for backend in backends {
-                let addr = backend.addr.clone();
-                let curr_weight = backend.current_weight.load(Ordering::Relaxed);
-
-                let result =
-                    Self::get_current_backend_latency(&addr, self.threshold.latency_critical_ms)
-                        .await;
-
-                match result {
-                    Some(latency) => {
-                        // Update average latency with exponential moving average
-                        let current_avg = backend.avg_latency_ms.load(Ordering::Relaxed);
-                        let new_avg = if current_avg == 0 {
-                            latency as usize
+                let health = self.clone();
+                let permits = permits.clone();
+                probes.spawn(async move {
+                    sleep(health.jitter_delay()).await;
+                    let _permit = permits.acquire_owned().await?;
+                    health.check(backend).await
+                });
+            }
+            while let Some(result) = probes.join_next().await {
+                result??;
+            }
+
+            if let Some(interval) = self.balancer.health_summary_interval_ms
+                && last_summary.elapsed() >= Duration::from_millis(interval)
+            {
+                self.send_summary().await?;
+                last_summary = Instant::now();
+            }
+
+            sleep(Duration::from_millis(self.balancer.check_interval_ms)).await;
+        }
+    }
+
+    /// Random delay of up to `health_jitter` of the check interval, spreading probes across the cycle.
+    fn jitter_delay(&self) -> Duration {
+        let jitter = self.balancer.health_jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return Duration::ZERO;
+        }
+        let max = self.balancer.check_interval_ms as f64 * jitter;
+        Duration::from_millis(rand::random_range(0.0..max) as u64)
+    }
+
+    /// Probes one backend and applies the result to its weight, breaker and state.
+    async fn check(&self, backend: Arc<Backend>) -> anyhow::Result<()> {
+        self.poll_agent(&backend).await?;
+
+        // Backends under maintenance are expected to be unreachable, so probing them only adds noise.
+        if backend.state() == BackendState::Maintenance {
+            return Ok(());
+        }
+
+        let addr = backend.addr.clone();
+        let curr_weight = backend.current_weight.load(Ordering::Relaxed);
+
+        let result = self.probe(&backend).await;
+
+        match result {
+            Some(latency) => {
+                // A down backend stays out of rotation until it passed `rise` checks in a row.
+                let successes = backend.record_probe_success();
+                if backend.state() == BackendState::Down && successes < self.threshold.rise {
+                    return Ok(());
+                }
+
+                let cool_down = Duration::from_millis(self.threshold.breaker_open_ms);
+                if let Some(state) = backend.breaker.on_probe_success(cool_down) {
+                    self.health_tx
+                        .send(ExporterEvent::breaker_transition(addr.clone(), state))
+                        .await?;
+                }
+
+                // Update average latency with exponential moving average
+                let current_avg = backend.avg_latency_ms.load(Ordering::Relaxed);
+                let new_avg = if current_avg == 0 {
+                    latency as usize
+                } else {
+                    // EMA with alpha = 0.3 (30% weight to new measurement)
+                    (current_avg * 7 + latency as usize * 3) / 10
+                };
+                backend.avg_latency_ms.store(new_avg, Ordering::Relaxed);
+
+                match backend.exceeds_latency_threshold(latency, self.threshold.latency_critical_ms)
+                {
+                    // If the threshold exceeds then reducing the wight gracefully.
+                    // In consistent critical latency,It the wight become 0 then it will be stopped routing.
+                    true => {
+                        let new_wight = curr_weight.saturating_sub(self.threshold.recovery_step);
+
+                        if new_wight != curr_weight {
+                            backend.current_weight.swap(new_wight, Ordering::Relaxed);
+
+                            self.health_tx
+                                .send(ExporterEvent::WeightDecreased {
+                                    addr: addr.clone(),
+                                    old: curr_weight,
+                                    new: new_wight,
+                                    latency_ms: latency,
+                                })
+                                .await?;
+                        }
+
+                        self.transition(&backend, BackendState::Degraded).await?;
+                    }
+                    // If the latency is good (not exceeded) and the wight is low which shows booting or adjusting.
+                    // so that setting wight incrementally
+                    false => {
+                        // With slow start the ramp runs on wall-clock time, so the weight is restored at once.
+                        let weight_cap = backend.weight_cap();
+                        let slow_start = Duration::from_millis(self.threshold.slow_start_ms);
+                        let step = if slow_start.is_zero() {
+                            self.threshold.recovery_step
                         } else {
-                            // EMA with alpha = 0.3 (30% weight to new measurement)
-                            (current_avg * 7 + latency as usize * 3) / 10
+                            weight_cap
                         };
-                        backend.avg_latency_ms.store(new_avg, Ordering::Relaxed);
-
-                        match backend
-                            .exceeds_latency_threshold(latency, self.threshold.latency_critical_ms)
-                        {
-                            // If the threshold exceeds then reducing the wight gracefully.
-                            // In consistent critical latency,It the wight become 0 then it will be stopped routing.
-                            true => {
-                                let new_wight =
-                                    std::cmp::max(0, curr_weight - self.threshold.recovery_step);
-                                backend.current_weight.swap(new_wight, Ordering::Relaxed);
-
-                                self.health_tx
-                                    .send(ExporterEvent::WeightDecreased {
-                                        addr: addr.clone(),
-                                        old: curr_weight,
-                                        new: new_wight,
-                                        latency_ms: latency,
-                                    })
-                                    .await?;
-                            }
-                            // If the latency is good (not exceeded) and the wight is low which shows booting or adjusting.
-                            // so that setting wight incrementally
-                            false => {
-                                if backend.is_weight_low(curr_weight) {
-                                    let new_weight = std::cmp::max(
-                                        backend.base_weight,
-                                        curr_weight + self.threshold.recovery_step,
-                                    );
-                                    backend.current_weight.swap(new_weight, Ordering::Relaxed);
-
-                                    self.health_tx
-                                        .send(ExporterEvent::WeightIncreased {
-                                            addr: addr.clone(),
-                                            old: curr_weight,
-                                            new: new_weight,
-                                            latency_ms: latency,
-                                        })
-                                        .await?;
-                                }
+                        let new_weight = std::cmp::min(weight_cap, curr_weight + step);
+
+                        if backend.is_weight_low(curr_weight) && new_weight > curr_weight {
+                            backend.current_weight.swap(new_weight, Ordering::Relaxed);
+                            // Weight back from 0 (e.g. after an error-rate ejection) means the
+                            // backend took no traffic, so it ramps up again.
+                            if curr_weight == 0 {
+                                backend.start_slow_start();
                             }
+
+                            self.health_tx
+                                .send(ExporterEvent::WeightIncreased {
+                                    addr: addr.clone(),
+                                    old: curr_weight,
+                                    new: new_weight,
+                                    latency_ms: latency,
+                                })
+                                .await?;
                         }
-                    }
-                    None => {
-                        // If any error occurred while performing ping then setting the wight = 0.
-                        // Which means, applying circuit breaker unless it warm up.
-                        backend.current_weight.swap(0, Ordering::Relaxed);
-
-                        self.health_tx
-                            .send(ExporterEvent::BackendDown { addr: addr.clone() })
-                            .await?;
+
+                        // A backend coming back from down starts its window with this transition.
+                        let ramping = !slow_start.is_zero()
+                            && (!backend.state().is_routable()
+                                || backend.is_slow_starting(slow_start));
+                        let state = if new_weight >= weight_cap && !ramping {
+                            BackendState::Healthy
+                        } else {
+                            BackendState::Booting
+                        };
+                        self.transition(&backend, state).await?;
                     }
                 }
             }
-            sleep(Duration::from_millis(self.balancer.check_interval_ms)).await;
+            None => {
+                // Only `fall` failures in a row take the backend down, so a single dropped
+                // probe does not cause an outage.
+                if backend.record_probe_failure() < self.threshold.fall {
+                    return Ok(());
+                }
+
+                // If any error occurred while performing ping then setting the wight = 0.
+                // Which means, applying circuit breaker unless it warm up.
+                backend.current_weight.swap(0, Ordering::Relaxed);
+
+                self.transition(&backend, BackendState::Down).await?;
+
+                if let Some(state) = backend.breaker.trip() {
+                    self.health_tx
+                        .send(ExporterEvent::breaker_transition(addr.clone(), state))
+                        .await?;
+                }
+            }
         }
+
+        Ok(())
     }
-    async fn get_current_backend_latency(addr: &str, timeout_threshold: u64) -> Option<u64> {
-        let timeout_dur = Duration::from_millis(timeout_threshold);
-        let start = Instant::now();
 
-        let connect_result = timeout(timeout_dur, TcpStream::connect(addr)).await;
+    /// Applies a health-derived state and reports only the edge, so a backend that stays down
+    /// produces one `BackendDown` rather than one per check interval.
+    async fn transition(&self, backend: &Backend, state: BackendState) -> anyhow::Result<()> {
+        let Some(old) = backend.transition_health(state) else {
+            return Ok(());
+        };
+
+        let addr = backend.addr.clone();
+        let event = match (old, state) {
+            (_, BackendState::Down) => ExporterEvent::BackendDown { addr },
+            (BackendState::Down, _) => ExporterEvent::BackendUp { addr, state },
+            (_, BackendState::Degraded) => ExporterEvent::BackendDegraded { addr },
+            _ => ExporterEvent::StateChanged {
+                addr,
+                old,
+                new: state,
+            },
+        };
+        self.health_tx.send(event).await?;
+        Ok(())
+    }
 
-        match connect_result {
-            Ok(Ok(_stream)) => Some(start.elapsed().as_millis() as u64),
-            Ok(Err(_)) => None,
-            Err(_) => None,
+    async fn send_summary(&self) -> anyhow::Result<()> {
+        let mut counts = [0usize; 6];
+        for backend in self.backends.read().await.iter() {
+            counts[backend.state() as usize] += 1;
         }
+
+        self.health_tx
+            .send(ExporterEvent::HealthSummary {
+                healthy: counts[BackendState::Healthy as usize],
+                degraded: counts[BackendState::Degraded as usize],
+                booting: counts[BackendState::Booting as usize],
+                down: counts[BackendState::Down as usize],
+                draining: counts[BackendState::Draining as usize],
+                maintenance: counts[BackendState::Maintenance as usize],
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Applies the weight and drain/maintenance requests of the backend's agent, if it has one.
+    /// An unreachable agent changes nothing, so the regular health check stays in charge.
+    async fn poll_agent(&self, backend: &Backend) -> anyhow::Result<()> {
+        let Some(agent) = backend.agent.read().unwrap().clone() else {
+            return Ok(());
+        };
+        let limit =
+            Duration::from_millis(agent.timeout_ms.unwrap_or(self.balancer.health_timeout_ms));
+        let target = agent.target(&backend.addr);
+        let Ok(Ok(report)) = timeout(limit, agent::query(&target, agent.send.as_deref())).await
+        else {
+            return Ok(());
+        };
+
+        if let Some(pct) = report.weight_pct {
+            let old = backend.agent_weight_pct.swap(pct, Ordering::Relaxed);
+            if old != pct {
+                backend
+                    .current_weight
+                    .fetch_min(backend.weight_cap(), Ordering::Relaxed);
+                self.health_tx
+                    .send(ExporterEvent::AgentWeightChanged {
+                        addr: backend.addr.clone(),
+                        old_pct: old,
+                        new_pct: pct,
+                    })
+                    .await?;
+            }
+        }
+
+        let (old, new) = match report.command {
+            Some(AgentCommand::Drain) => (
+                backend.agent_hold(BackendState::Draining),
+                BackendState::Draining,
+            ),
+            Some(AgentCommand::Maint) => (
+                backend.agent_hold(BackendState::Maintenance),
+                BackendState::Maintenance,
+            ),
+            Some(AgentCommand::Up) => (backend.agent_release(), BackendState::Booting),
+            None => (None, backend.state()),
+        };
+        if let Some(old) = old {
+            self.health_tx
+                .send(ExporterEvent::StateChanged {
+                    addr: backend.addr.clone(),
+                    old,
+                    new,
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Runs the backend's health check, returning its latency in milliseconds when it passed.
+    async fn probe(&self, backend: &Backend) -> Option<u64> {
+        let check = backend.health_check.read().unwrap().clone();
+        let addr = backend.health_addr.read().unwrap().clone();
+        let limit = check
+            .timeout()
+            .unwrap_or(Duration::from_millis(self.balancer.health_timeout_ms));
+        timeout(limit, check.check(&addr)).await.ok()?.ok()
     }
 }
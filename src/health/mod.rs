@@ -1 +1,6 @@
+pub mod agent;
+pub mod checks;
+pub mod factories;
+#[allow(clippy::module_inception)]
 pub mod health;
+pub mod traits;
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// A probe the health monitor runs against a backend every check interval.
+///
+/// Implementations report the observed latency in milliseconds when the backend passed, which
+/// feeds the same weight adjustment regardless of how the backend was probed.
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    async fn check(&self, addr: &str) -> anyhow::Result<u64>;
+
+    /// Time the whole check may take; `None` falls back to `balancer.health_timeout_ms`.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+}
@@ -1,6 +1,9 @@
+pub mod admin;
 pub mod algorithms;
 pub mod balancer;
+pub mod cli;
 pub mod config;
 pub mod health;
 pub mod logging;
+pub mod reload;
 pub mod state;
@@ -1,3 +1,5 @@
+use crate::state::backend_state::BackendState;
+use crate::state::circuit_breaker::BreakerState;
 use std::fmt;
 
 #[derive(Debug)]
@@ -17,11 +19,95 @@ pub enum ExporterEvent {
     BackendDown {
         addr: String,
     },
+    BackendUp {
+        addr: String,
+        state: BackendState,
+    },
+    BackendDegraded {
+        addr: String,
+    },
+    HealthSummary {
+        healthy: usize,
+        degraded: usize,
+        booting: usize,
+        down: usize,
+        draining: usize,
+        maintenance: usize,
+    },
+    BackendEjected {
+        addr: String,
+        failures: u64,
+        total: u64,
+    },
+    BreakerOpened {
+        addr: String,
+    },
+    BreakerHalfOpened {
+        addr: String,
+    },
+    BreakerClosed {
+        addr: String,
+    },
+    ConnectFailed {
+        addr: String,
+        attempt: u64,
+        err: String,
+    },
+    BackendAdded {
+        id: String,
+        addr: String,
+    },
+    BackendRemoved {
+        id: String,
+        addr: String,
+    },
+    AgentWeightChanged {
+        addr: String,
+        old_pct: u64,
+        new_pct: u64,
+    },
+    WeightSet {
+        id: String,
+        old: u64,
+        new: u64,
+    },
+    StateChanged {
+        addr: String,
+        old: BackendState,
+        new: BackendState,
+    },
+    ConfigReloaded {
+        added: Vec<String>,
+        removed: Vec<String>,
+        updated: Vec<String>,
+        algorithm: String,
+    },
+    ConfigReloadFailed {
+        err: String,
+    },
+    ShutdownStarted {
+        active: usize,
+    },
+    ShutdownComplete {
+        drained: usize,
+        force_closed: usize,
+    },
     Error {
         err: String,
     },
 }
 
+impl ExporterEvent {
+    /// Maps the state a circuit breaker moved into onto its transition event.
+    pub fn breaker_transition(addr: String, state: BreakerState) -> Self {
+        match state {
+            BreakerState::Open => ExporterEvent::BreakerOpened { addr },
+            BreakerState::HalfOpen => ExporterEvent::BreakerHalfOpened { addr },
+            BreakerState::Closed => ExporterEvent::BreakerClosed { addr },
+        }
+    }
+}
+
 impl fmt::Display for ExporterEvent {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let log_message = match self {
@@ -50,6 +136,107 @@ impl fmt::Display for ExporterEvent {
             ExporterEvent::BackendDown { addr } => {
                 format!("level=warn event=BackendDown addr={}", addr)
             }
+            ExporterEvent::BackendUp { addr, state } => {
+                format!("level=info event=BackendUp addr={} state={}", addr, state)
+            }
+            ExporterEvent::BackendDegraded { addr } => {
+                format!("level=warn event=BackendDegraded addr={}", addr)
+            }
+            ExporterEvent::HealthSummary {
+                healthy,
+                degraded,
+                booting,
+                down,
+                draining,
+                maintenance,
+            } => {
+                format!(
+                    "level=info event=HealthSummary healthy={} degraded={} booting={} down={} draining={} maintenance={}",
+                    healthy, degraded, booting, down, draining, maintenance
+                )
+            }
+            ExporterEvent::BackendEjected {
+                addr,
+                failures,
+                total,
+            } => {
+                format!(
+                    "level=warn event=BackendEjected addr={} failures={} total={}",
+                    addr, failures, total
+                )
+            }
+            ExporterEvent::BreakerOpened { addr } => {
+                format!("level=warn event=BreakerOpened addr={}", addr)
+            }
+            ExporterEvent::BreakerHalfOpened { addr } => {
+                format!("level=info event=BreakerHalfOpened addr={}", addr)
+            }
+            ExporterEvent::BreakerClosed { addr } => {
+                format!("level=info event=BreakerClosed addr={}", addr)
+            }
+            ExporterEvent::ConnectFailed { addr, attempt, err } => {
+                format!(
+                    "level=warn event=ConnectFailed addr={} attempt={} err=\"{}\"",
+                    addr, attempt, err
+                )
+            }
+            ExporterEvent::BackendAdded { id, addr } => {
+                format!("level=info event=BackendAdded id={} addr={}", id, addr)
+            }
+            ExporterEvent::BackendRemoved { id, addr } => {
+                format!("level=info event=BackendRemoved id={} addr={}", id, addr)
+            }
+            ExporterEvent::AgentWeightChanged {
+                addr,
+                old_pct,
+                new_pct,
+            } => {
+                format!(
+                    "level=info event=AgentWeightChanged addr={} old_pct={} new_pct={}",
+                    addr, old_pct, new_pct
+                )
+            }
+            ExporterEvent::WeightSet { id, old, new } => {
+                format!(
+                    "level=info event=WeightSet id={} old={} new={}",
+                    id, old, new
+                )
+            }
+            ExporterEvent::StateChanged { addr, old, new } => {
+                format!(
+                    "level=info event=StateChanged addr={} old={} new={}",
+                    addr, old, new
+                )
+            }
+            ExporterEvent::ConfigReloaded {
+                added,
+                removed,
+                updated,
+                algorithm,
+            } => {
+                format!(
+                    "level=info event=ConfigReloaded added=[{}] removed=[{}] updated=[{}] algorithm={}",
+                    added.join(","),
+                    removed.join(","),
+                    updated.join(","),
+                    algorithm
+                )
+            }
+            ExporterEvent::ConfigReloadFailed { err } => {
+                format!("level=error event=ConfigReloadFailed err=\"{}\"", err)
+            }
+            ExporterEvent::ShutdownStarted { active } => {
+                format!("level=info event=ShutdownStarted active={}", active)
+            }
+            ExporterEvent::ShutdownComplete {
+                drained,
+                force_closed,
+            } => {
+                format!(
+                    "level=info event=ShutdownComplete drained={} force_closed={}",
+                    drained, force_closed
+                )
+            }
             ExporterEvent::Error { err } => {
                 format!("level=error event=Error err=\"{}\"", err)
             }
@@ -1 +1 @@
-pub mod exporter_event;
\ No newline at end of file
+pub mod exporter_event;
@@ -1,26 +1,57 @@
+use clap::Parser;
+use monad_load_balancer::admin::admin_server::AdminServer;
 use monad_load_balancer::algorithms::factories::algorithm::Algorithm;
 use monad_load_balancer::balancer::balancer::Balancer;
+use monad_load_balancer::cli::cli_args::{CliArgs, Command};
 use monad_load_balancer::config::load_balancer_cfg::LoadBalancerCfg;
-use monad_load_balancer::health::health::Health;
 use monad_load_balancer::logging::events::exporter_event::ExporterEvent;
 use monad_load_balancer::logging::exporters::console_exporter::ConsoleExporter;
 use monad_load_balancer::logging::exporters::file_exporter::FileExporter;
 use monad_load_balancer::logging::logging_exporter::Exporter;
 use monad_load_balancer::logging::traits::log_exporter::LogExporter;
+use monad_load_balancer::reload::config_reloader::ConfigReloader;
 use monad_load_balancer::state::backend::Backend;
+use std::process;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::sync::{RwLock, mpsc};
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+use tokio::{select, signal};
 
 #[tokio::main]
 async fn main() {
-    let (exporter_tx, exporter_rx): (Sender<ExporterEvent>, Receiver<ExporterEvent>) =
-        mpsc::channel(8 * 1024);
+    let cli = CliArgs::parse();
 
-    let cfg = LoadBalancerCfg::load()
+    let cfg = match cli
+        .load_cfg()
         .await
-        .expect("load balancer cfg failed");
+        .and_then(|cfg| cfg.validate().map(|_| cfg))
+    {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            eprintln!("invalid configuration {}: {:#}", cli.config.display(), e);
+            process::exit(1);
+        }
+    };
+
+    match cli.command {
+        Some(Command::Validate) => println!("{} is valid", cli.config.display()),
+        Some(Command::PrintEffectiveConfig) => {
+            print!(
+                "{}",
+                serde_yaml::to_string(&cfg).expect("failed to render config")
+            )
+        }
+        Some(Command::Run) | None => run(cli, cfg).await,
+    }
+}
+
+async fn run(cli: CliArgs, cfg: LoadBalancerCfg) {
+    let (exporter_tx, exporter_rx): (Sender<ExporterEvent>, Receiver<ExporterEvent>) =
+        mpsc::channel(8 * 1024);
 
     let server_port = format!("0.0.0.0:{}", cfg.balancer_cfg.port);
 
@@ -33,49 +64,74 @@ async fn main() {
         Arc::new(RwLock::new(backend))
     };
 
-    {
-        let config = cfg.clone();
-        let health_backends = backends.clone();
-        let health_tx = exporter_tx.clone();
+    let log_file = cli.log_file.clone();
+    let exporter_handle = tokio::spawn(async move {
+        let exporters: Vec<Arc<dyn LogExporter + Send + Sync>> = vec![
+            Arc::new(FileExporter::new(log_file)),
+            Arc::new(ConsoleExporter),
+        ];
 
-        tokio::spawn(async move {
-            let health = Health::new(
-                config.thresholds_cfg,
-                config.balancer_cfg,
-                health_backends,
-                health_tx,
-            );
-
-            health.monitor().await.unwrap();
-        });
+        let exporter = Exporter::new(exporters);
+        exporter.run(exporter_rx).await.unwrap();
+    });
 
+    let admin_handle = cfg.admin_cfg.clone().map(|admin_cfg| {
+        let admin = AdminServer::new(admin_cfg, backends.clone(), exporter_tx.clone());
         tokio::spawn(async move {
-            let exporters: Vec<Arc<dyn LogExporter + Send + Sync>> = vec![
-                Arc::new(FileExporter::new("./log.txt")),
-                Arc::new(ConsoleExporter),
-            ];
-
-            let exporter = Exporter::new(exporters);
-            exporter.run(exporter_rx).await.unwrap();
-        });
-    }
+            admin.serve().await.unwrap();
+        })
+    });
 
-    let algorithm = Algorithm::select(cfg.balancer_cfg.algorithm);
-    let balancer = Balancer::new(algorithm, backends);
+    let algorithm = Algorithm::select(
+        cfg.balancer_cfg.algorithm.clone(),
+        &cfg.balancer_cfg.algorithm_options,
+    );
+    let balancer = Balancer::new(
+        algorithm,
+        backends.clone(),
+        cfg.balancer_cfg.retry.clone(),
+        cfg.thresholds_cfg.clone(),
+        exporter_tx.clone(),
+    );
     let health_tx = exporter_tx.clone();
 
+    let reloader = Arc::new(ConfigReloader::new(
+        cli,
+        cfg.clone(),
+        backends,
+        balancer.clone(),
+        exporter_tx.clone(),
+    ));
+    reloader.start_health().await;
+
+    let reload_handle = {
+        let reloader = reloader.clone();
+        tokio::spawn(async move {
+            reloader.watch().await.unwrap();
+        })
+    };
+
     let listener = TcpListener::bind(&server_port)
         .await
         .expect("failed to bind server port, Please make sure address is available");
 
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    let mut connections = JoinSet::new();
+
     loop {
-        let (client, client_addr) = listener.accept().await.unwrap();
+        let (client, client_addr) = select! {
+            _ = &mut shutdown => break,
+            // Reap finished connections so the set only tracks in-flight ones.
+            Some(_) = connections.join_next(), if !connections.is_empty() => continue,
+            accepted = listener.accept() => accepted.unwrap(),
+        };
         println!("Accepted connection from {}", client_addr);
 
         let balancer = balancer.clone();
         let health_tx = health_tx.clone();
 
-        tokio::spawn(async move {
+        connections.spawn(async move {
             if let Err(e) = balancer.route_connection(client).await {
                 health_tx
                     .send(ExporterEvent::Error {
@@ -86,4 +142,71 @@ async fn main() {
             }
         });
     }
+
+    // Stop accepting and give in-flight connections until the drain deadline to finish on their own.
+    drop(listener);
+    reload_handle.abort();
+    reloader.stop_health().await;
+    if let Some(admin_handle) = &admin_handle {
+        admin_handle.abort();
+    }
+
+    let active = connections.len();
+    exporter_tx
+        .send(ExporterEvent::ShutdownStarted { active })
+        .await
+        .unwrap();
+
+    let drain_timeout = Duration::from_millis(cfg.balancer_cfg.drain_timeout_ms);
+    let _ = timeout(drain_timeout, async {
+        while connections.join_next().await.is_some() {}
+    })
+    .await;
+
+    let force_closed = connections.len();
+    connections.shutdown().await;
+
+    exporter_tx
+        .send(ExporterEvent::ShutdownComplete {
+            drained: active - force_closed,
+            force_closed,
+        })
+        .await
+        .unwrap();
+
+    // Dropping the last senders closes the channel, letting the exporter flush what is queued and exit.
+    drop(exporter_tx);
+    drop(health_tx);
+    drop(balancer);
+    let _ = reload_handle.await;
+    drop(reloader);
+    if let Some(admin_handle) = admin_handle {
+        let _ = admin_handle.await;
+    }
+    exporter_handle.await.unwrap();
+}
+
+/// Resolves once the process receives SIGINT (Ctrl+C) or, on unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
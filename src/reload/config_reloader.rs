@@ -0,0 +1,277 @@
+use crate::algorithms::factories::algorithm::Algorithm;
+use crate::balancer::balancer::Balancer;
+use crate::cli::cli_args::CliArgs;
+use crate::config::backend_cfg::BackendCfg;
+use crate::config::load_balancer_cfg::LoadBalancerCfg;
+use crate::health::factories::check::Check;
+use crate::health::health::Health;
+use crate::logging::events::exporter_event::ExporterEvent;
+use crate::state::backend::Backend;
+use crate::state::backend_state::BackendState;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+/// Applies a changed config file to the running balancer without dropping connections.
+///
+/// Backends are matched on `id` and `address`, and only what changed in the file since the last
+/// load is applied: changed entries are updated in place, new entries are added and dropped ones
+/// are drained out of the pool. Backends added, removed or reweighted through the admin API keep
+/// those changes until the file itself changes the same backend. The algorithm and retry policy are
+/// swapped on the [`Balancer`] and the [`Health`] loop is restarted with the new thresholds.
+/// `balancer.port` and `admin` are bound once at startup and need a restart to change.
+/// Command-line overrides are applied again on every reload.
+pub struct ConfigReloader {
+    cli: CliArgs,
+    current: Mutex<LoadBalancerCfg>,
+    backends: Arc<RwLock<Vec<Arc<Backend>>>>,
+    balancer: Balancer,
+    health_handle: Mutex<Option<JoinHandle<()>>>,
+    reload_tx: Sender<ExporterEvent>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        cli: CliArgs,
+        cfg: LoadBalancerCfg,
+        backends: Arc<RwLock<Vec<Arc<Backend>>>>,
+        balancer: Balancer,
+        reload_tx: Sender<ExporterEvent>,
+    ) -> Self {
+        Self {
+            cli,
+            current: Mutex::new(cfg),
+            backends,
+            balancer,
+            health_handle: Mutex::new(None),
+            reload_tx,
+        }
+    }
+
+    /// Starts (or restarts) the health monitor with the current thresholds.
+    pub async fn start_health(&self) {
+        let cfg = self.current.lock().await.clone();
+        let health = Arc::new(Health::new(
+            cfg.thresholds_cfg,
+            cfg.balancer_cfg,
+            self.backends.clone(),
+            self.reload_tx.clone(),
+        ));
+
+        let mut handle = self.health_handle.lock().await;
+        if let Some(previous) = handle.take() {
+            previous.abort();
+        }
+        *handle = Some(tokio::spawn(async move {
+            health.monitor().await.unwrap();
+        }));
+    }
+
+    pub async fn stop_health(&self) {
+        if let Some(handle) = self.health_handle.lock().await.take() {
+            handle.abort();
+            let _ = handle.await;
+        }
+    }
+
+    /// Reloads on SIGHUP and, when `watch_config_ms` is set, whenever the file's mtime changes.
+    pub async fn watch(&self) -> anyhow::Result<()> {
+        #[cfg(unix)]
+        let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())?;
+        let mut last_modified = self.modified().await;
+
+        loop {
+            let poll = self.current.lock().await.balancer_cfg.watch_config_ms;
+
+            let file_changed = async {
+                match poll {
+                    Some(ms) => sleep(Duration::from_millis(ms)).await,
+                    None => std::future::pending::<()>().await,
+                }
+            };
+
+            #[cfg(unix)]
+            let hangup = hangup.recv();
+            #[cfg(not(unix))]
+            let hangup = std::future::pending::<Option<()>>();
+
+            tokio::select! {
+                _ = hangup => {},
+                _ = file_changed => {
+                    let modified = self.modified().await;
+                    if modified == last_modified {
+                        continue;
+                    }
+                }
+            }
+
+            last_modified = self.modified().await;
+            self.reload().await?;
+        }
+    }
+
+    /// Re-reads the config file and applies it, keeping the running config if it fails to load or validate.
+    pub async fn reload(&self) -> anyhow::Result<()> {
+        let loaded = self
+            .cli
+            .load_cfg()
+            .await
+            .and_then(|cfg| cfg.validate().map(|_| cfg));
+        let cfg = match loaded {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                self.reload_tx
+                    .send(ExporterEvent::ConfigReloadFailed { err: e.to_string() })
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let previous = self.current.lock().await.clone();
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut updated = Vec::new();
+        {
+            let mut wg = self.backends.write().await;
+
+            wg.retain(|backend| {
+                let was_listed = find_cfg(&previous.backends, &backend.id, &backend.addr);
+                let listed = find_cfg(&cfg.backends, &backend.id, &backend.addr);
+                // A file entry now claiming the same id or address takes over from this backend.
+                let displaced = cfg
+                    .backends
+                    .iter()
+                    .any(|b| b.id == backend.id || b.address == backend.addr);
+                // Backends added through the admin API are not the file's to remove.
+                let keep = listed.is_some() || (was_listed.is_none() && !displaced);
+                if !keep {
+                    // Connections already routed hold their own `Arc`, so they finish normally.
+                    backend.set_state(BackendState::Draining);
+                    removed.push(backend.id.clone());
+                }
+                keep
+            });
+
+            for backend_cfg in &cfg.backends {
+                let before = find_cfg(&previous.backends, &backend_cfg.id, &backend_cfg.address);
+                let existing = wg
+                    .iter()
+                    .find(|b| b.id == backend_cfg.id && b.addr == backend_cfg.address);
+
+                match existing {
+                    Some(backend) => {
+                        // Weight and max_conn may have been set through the admin API, so they
+                        // are only overwritten when the file itself changed them.
+                        let mut limits_changed = false;
+                        if before.is_none_or(|b| b.weight != backend_cfg.weight) {
+                            let old_weight = backend
+                                .base_weight
+                                .swap(backend_cfg.weight, Ordering::Relaxed);
+                            // Health only ever raises weight towards base, so a lowered base is applied here.
+                            backend
+                                .current_weight
+                                .fetch_min(backend_cfg.weight, Ordering::Relaxed);
+                            limits_changed |= old_weight != backend_cfg.weight;
+                        }
+                        if before.is_none_or(|b| b.max_conn != backend_cfg.max_conn) {
+                            let old_max_conn = backend
+                                .max_conn
+                                .swap(backend_cfg.max_conn, Ordering::Relaxed);
+                            limits_changed |= old_max_conn != backend_cfg.max_conn;
+                        }
+
+                        // The check is only rebuilt when its config changed, keeping any check
+                        // installed through the library API on backends the file leaves alone.
+                        let check_changed =
+                            before.is_none_or(|b| b.health_check != backend_cfg.health_check);
+                        if check_changed {
+                            *backend.health_check.write().unwrap() =
+                                Check::select(backend_cfg.health_check.clone().unwrap_or_default());
+                        }
+
+                        let health_addr = backend_cfg.health_target();
+                        let addr_changed = {
+                            let mut current = backend.health_addr.write().unwrap();
+                            let changed = *current != health_addr;
+                            *current = health_addr;
+                            changed
+                        };
+
+                        let agent_changed = {
+                            let mut current = backend.agent.write().unwrap();
+                            let changed = *current != backend_cfg.agent;
+                            *current = backend_cfg.agent.clone();
+                            changed
+                        };
+                        if agent_changed && backend_cfg.agent.is_none() {
+                            backend.agent_weight_pct.store(100, Ordering::Relaxed);
+                        }
+
+                        let retry_changed = {
+                            let mut current = backend.retry.write().unwrap();
+                            let changed = *current != backend_cfg.retry;
+                            *current = backend_cfg.retry.clone();
+                            changed
+                        };
+
+                        if limits_changed
+                            || check_changed
+                            || addr_changed
+                            || agent_changed
+                            || retry_changed
+                        {
+                            updated.push(backend.id.clone());
+                        }
+                    }
+                    // Removed through the admin API since the last load; it stays removed.
+                    None if before.is_some() => {}
+                    None => {
+                        wg.push(Arc::new(Backend::from_cfg(backend_cfg)));
+                        added.push(backend_cfg.id.clone());
+                    }
+                }
+            }
+        }
+
+        self.balancer
+            .reconfigure(
+                Algorithm::select(
+                    cfg.balancer_cfg.algorithm.clone(),
+                    &cfg.balancer_cfg.algorithm_options,
+                ),
+                cfg.balancer_cfg.retry.clone(),
+                cfg.thresholds_cfg.clone(),
+            )
+            .await;
+
+        let algorithm = format!("{:?}", cfg.balancer_cfg.algorithm);
+        *self.current.lock().await = cfg;
+        self.start_health().await;
+
+        self.reload_tx
+            .send(ExporterEvent::ConfigReloaded {
+                added,
+                removed,
+                updated,
+                algorithm,
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn modified(&self) -> Option<SystemTime> {
+        tokio::fs::metadata(&self.cli.config)
+            .await
+            .and_then(|m| m.modified())
+            .ok()
+    }
+}
+
+/// The file entry for the backend with this `id` and `address`, if any.
+fn find_cfg<'a>(backends: &'a [BackendCfg], id: &str, addr: &str) -> Option<&'a BackendCfg> {
+    backends.iter().find(|b| b.id == id && b.address == addr)
+}
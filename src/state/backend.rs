@@ -1,15 +1,41 @@
+use crate::config::agent_cfg::AgentCfg;
 use crate::config::backend_cfg::BackendCfg;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use crate::config::retry_cfg::RetryOverrideCfg;
+use crate::config::thresholds_cfg::SlowStartCurve;
+use crate::health::factories::check::Check;
+use crate::health::traits::health_check::HealthCheck;
+use crate::state::backend_state::BackendState;
+use crate::state::circuit_breaker::CircuitBreaker;
+use crate::state::error_window::ErrorWindow;
+use crate::state::latency_estimate::LatencyEstimate;
+use std::sync::atomic::{AtomicU8, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
-#[derive(Debug)]
 pub struct Backend {
     pub id: String,
     pub addr: String,
-    pub max_conn: u64,
-    pub base_weight: u64,
+    pub max_conn: AtomicU64,
+    pub base_weight: AtomicU64,
     pub current_weight: AtomicU64,
     pub active_conn: AtomicU64,
     pub avg_latency_ms: AtomicUsize,
+    /// Latency of proxied connections, fed by the routing algorithm.
+    pub latency: LatencyEstimate,
+    pub state: AtomicU8,
+    pub consecutive_successes: AtomicU64,
+    pub consecutive_failures: AtomicU64,
+    pub errors: ErrorWindow,
+    pub breaker: CircuitBreaker,
+    pub retry: RwLock<Option<RetryOverrideCfg>>,
+    pub health_check: RwLock<Arc<dyn HealthCheck>>,
+    pub health_addr: RwLock<String>,
+    pub agent: RwLock<Option<AgentCfg>>,
+    pub agent_weight_pct: AtomicU64,
+    agent_state: Mutex<Option<BackendState>>,
+    slow_start_since: Mutex<Option<Instant>>,
+    /// Bits of the `f64` slow-start factor last computed by the balancer.
+    traffic_share: AtomicU64,
 }
 
 impl Backend {
@@ -17,11 +43,25 @@ impl Backend {
         Self {
             id: cfg.id.clone(),
             addr: cfg.address.clone(),
-            max_conn: cfg.max_conn,
-            base_weight: cfg.weight,
+            max_conn: AtomicU64::new(cfg.max_conn),
+            base_weight: AtomicU64::new(cfg.weight),
             current_weight: AtomicU64::new(cfg.weight),
             avg_latency_ms: AtomicUsize::new(0),
             active_conn: AtomicU64::new(0),
+            latency: LatencyEstimate::default(),
+            state: AtomicU8::new(BackendState::Healthy as u8),
+            consecutive_successes: AtomicU64::new(0),
+            consecutive_failures: AtomicU64::new(0),
+            errors: ErrorWindow::default(),
+            breaker: CircuitBreaker::default(),
+            retry: RwLock::new(cfg.retry.clone()),
+            health_addr: RwLock::new(cfg.health_target()),
+            agent: RwLock::new(cfg.agent.clone()),
+            agent_weight_pct: AtomicU64::new(100),
+            agent_state: Mutex::new(None),
+            slow_start_since: Mutex::new(None),
+            traffic_share: AtomicU64::new(1.0f64.to_bits()),
+            health_check: RwLock::new(Check::select(cfg.health_check.clone().unwrap_or_default())),
         }
     }
 
@@ -34,21 +74,11 @@ impl Backend {
     }
 
     pub fn is_weight_low(&self, current_weight: u64) -> bool {
-        current_weight <= self.base_weight
-    }
-
-    pub fn is_currently_booting(
-        &self,
-        critical_latency_threshold: u64,
-        curr_latency: u64,
-        current_weight: u64,
-    ) -> bool {
-        self.exceeds_latency_threshold(curr_latency, critical_latency_threshold)
-            && self.is_weight_low(current_weight)
+        current_weight <= self.base_weight.load(Ordering::Relaxed)
     }
 
     pub fn has_no_wight(&self) -> bool {
-        self.current_weight.load(Ordering::Relaxed) <= 0
+        self.current_weight.load(Ordering::Relaxed) == 0
     }
 
     pub fn has_some_wight(&self) -> bool {
@@ -56,6 +86,154 @@ impl Backend {
     }
 
     pub fn is_max_conn_reached(&self) -> bool {
-        self.active_conn.load(Ordering::Relaxed) > self.max_conn
+        self.active_conn.load(Ordering::Relaxed) >= self.max_conn.load(Ordering::Relaxed)
+    }
+
+    /// Atomically claims one of the backend's `max_conn` slots, or returns `None` when all are taken.
+    /// The slot is released when the returned guard is dropped, including during a panic.
+    pub fn try_reserve(self: &Arc<Self>) -> Option<ConnectionGuard> {
+        let max_conn = self.max_conn.load(Ordering::Relaxed);
+        self.active_conn
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (active < max_conn).then_some(active + 1)
+            })
+            .ok()?;
+        Some(ConnectionGuard {
+            backend: self.clone(),
+        })
+    }
+
+    /// Counts a passing health check, returning the current success streak.
+    pub fn record_probe_success(&self) -> u64 {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        self.consecutive_successes.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Counts a failing health check, returning the current failure streak.
+    pub fn record_probe_failure(&self) -> u64 {
+        self.consecutive_successes.store(0, Ordering::Relaxed);
+        self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn state(&self) -> BackendState {
+        BackendState::from_u8(self.state.load(Ordering::Relaxed))
+    }
+
+    /// Unconditionally moves to `state`, returning the previous one.
+    pub fn set_state(&self, state: BackendState) -> BackendState {
+        let old = BackendState::from_u8(self.state.swap(state as u8, Ordering::Relaxed));
+        self.on_state_change(old, state);
+        old
+    }
+
+    /// Moves to a health-derived `state` unless an operator state is set or nothing changes.
+    /// Returns the previous state when a transition happened.
+    pub fn transition_health(&self, state: BackendState) -> Option<BackendState> {
+        let old = self
+            .state
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |curr| {
+                let curr = BackendState::from_u8(curr);
+                (!curr.is_operator_state() && curr != state).then_some(state as u8)
+            })
+            .ok()
+            .map(BackendState::from_u8)?;
+        self.on_state_change(old, state);
+        Some(old)
+    }
+
+    /// The weight health recovery ramps towards: `base_weight` scaled by the agent's share.
+    pub fn weight_cap(&self) -> u64 {
+        let base = self.base_weight.load(Ordering::Relaxed);
+        base * self.agent_weight_pct.load(Ordering::Relaxed) / 100
+    }
+
+    /// Moves into an agent-requested `Draining` or `Maintenance` state, unless an operator
+    /// already set one. Returns the previous state when a transition happened.
+    pub fn agent_hold(&self, state: BackendState) -> Option<BackendState> {
+        let mut held = self.agent_state.lock().unwrap();
+        let curr = self.state();
+        if curr == state || (curr.is_operator_state() && *held != Some(curr)) {
+            return None;
+        }
+        *held = Some(state);
+        Some(self.set_state(state))
+    }
+
+    /// Leaves a state the agent requested earlier, returning to `Booting` so health checks
+    /// settle the rest. States set by an operator are left alone.
+    pub fn agent_release(&self) -> Option<BackendState> {
+        let mut held = self.agent_state.lock().unwrap();
+        let state = held.take()?;
+        let old = self
+            .state
+            .compare_exchange(
+                state as u8,
+                BackendState::Booting as u8,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .ok()
+            .map(BackendState::from_u8)?;
+        self.on_state_change(old, BackendState::Booting);
+        Some(old)
+    }
+
+    /// Share (0.0 - 1.0) of its normal traffic the backend should get while slow-starting.
+    pub fn slow_start_factor(&self, window: Duration, curve: SlowStartCurve) -> f64 {
+        match *self.slow_start_since.lock().unwrap() {
+            Some(since) if !window.is_zero() && since.elapsed() < window => {
+                curve.factor(since.elapsed().as_secs_f64() / window.as_secs_f64())
+            }
+            _ => 1.0,
+        }
+    }
+
+    pub fn is_slow_starting(&self, window: Duration) -> bool {
+        self.slow_start_since
+            .lock()
+            .unwrap()
+            .is_some_and(|since| since.elapsed() < window)
+    }
+
+    /// Starts the slow-start window, e.g. when the backend returns to rotation, its breaker
+    /// closes or its weight comes back from 0.
+    pub fn start_slow_start(&self) {
+        *self.slow_start_since.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Share (0.0 - 1.0) of its normal traffic the backend currently gets, as last refreshed by
+    /// [`Backend::refresh_traffic_share`]. Algorithms scale weights up and loads down by it.
+    pub fn traffic_share(&self) -> f64 {
+        f64::from_bits(self.traffic_share.load(Ordering::Relaxed))
+    }
+
+    pub fn refresh_traffic_share(&self, window: Duration, curve: SlowStartCurve) {
+        let share = self.slow_start_factor(window, curve);
+        self.traffic_share.store(share.to_bits(), Ordering::Relaxed);
+    }
+
+    /// `current_weight` scaled by the traffic share, at least 1 while the weight is not 0.
+    pub fn effective_weight(&self) -> u64 {
+        let weight = self.current_weight.load(Ordering::Relaxed);
+        let scaled = (weight as f64 * self.traffic_share()).round() as u64;
+        if weight == 0 { 0 } else { scaled.max(1) }
+    }
+
+    /// A backend returning to rotation starts its slow-start window.
+    fn on_state_change(&self, old: BackendState, new: BackendState) {
+        if !old.is_routable() && new.is_routable() {
+            self.start_slow_start();
+        }
+    }
+}
+
+/// A claimed connection slot on a [`Backend`], counted in `active_conn` until dropped.
+pub struct ConnectionGuard {
+    backend: Arc<Backend>,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.backend.active_conn.fetch_sub(1, Ordering::AcqRel);
     }
 }
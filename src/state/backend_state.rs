@@ -0,0 +1,61 @@
+use serde::Serialize;
+use std::fmt;
+
+/// What a backend is currently doing, as seen by health checks and operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum BackendState {
+    /// Passing health checks at full weight.
+    Healthy = 0,
+    /// Reachable but slower than `latency_critical_ms`; weight is being reduced.
+    Degraded = 1,
+    /// Reachable again after an outage; weight is ramping back up.
+    Booting = 2,
+    /// Failing health checks; receives no traffic.
+    Down = 3,
+    /// Taken out of rotation by an operator or a reload; in-flight connections finish.
+    Draining = 4,
+    /// Taken out of rotation by an operator and not health checked.
+    Maintenance = 5,
+}
+
+impl BackendState {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0 => BackendState::Healthy,
+            1 => BackendState::Degraded,
+            2 => BackendState::Booting,
+            3 => BackendState::Down,
+            4 => BackendState::Draining,
+            _ => BackendState::Maintenance,
+        }
+    }
+
+    /// Whether new connections may be routed to a backend in this state.
+    pub fn is_routable(&self) -> bool {
+        matches!(
+            self,
+            BackendState::Healthy | BackendState::Degraded | BackendState::Booting
+        )
+    }
+
+    /// Operator states are only left through the admin API, never by health checks.
+    pub fn is_operator_state(&self) -> bool {
+        matches!(self, BackendState::Draining | BackendState::Maintenance)
+    }
+}
+
+impl fmt::Display for BackendState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            BackendState::Healthy => "healthy",
+            BackendState::Degraded => "degraded",
+            BackendState::Booting => "booting",
+            BackendState::Down => "down",
+            BackendState::Draining => "draining",
+            BackendState::Maintenance => "maintenance",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Traffic flows normally.
+    Closed,
+    /// The backend receives no traffic until the cool-down elapses and a health check passes.
+    Open,
+    /// A limited number of trial connections decide whether to close or re-open.
+    HalfOpen,
+}
+
+impl fmt::Display for BreakerState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half_open",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Permission for one connection, granted by [`CircuitBreaker::admit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// The breaker is closed; the outcome does not concern it.
+    Regular,
+    /// A half-open trial, tagged with the half-open period that granted it so an outcome
+    /// reported after the breaker re-opened cannot count towards a later period.
+    Trial(u64),
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: BreakerState,
+    opened_at: Instant,
+    half_open_period: u64,
+    trials_in_flight: u64,
+    trial_successes: u64,
+}
+
+/// Per-backend closed/open/half-open circuit breaker.
+///
+/// Every method that changes the state returns the new state so callers can report the transition.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    inner: Mutex<BreakerInner>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                opened_at: Instant::now(),
+                half_open_period: 0,
+                trials_in_flight: 0,
+                trial_successes: 0,
+            }),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn state(&self) -> BreakerState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Whether a new connection could currently be admitted, without claiming anything.
+    pub fn allows_traffic(&self, max_trials: u64) -> bool {
+        let inner = self.inner.lock().unwrap();
+        match inner.state {
+            BreakerState::Closed => true,
+            BreakerState::Open => false,
+            BreakerState::HalfOpen => inner.trials_in_flight < max_trials,
+        }
+    }
+
+    /// Claims permission for one connection. A [`Admission::Trial`] must be settled through
+    /// [`CircuitBreaker::on_success`] or [`CircuitBreaker::on_failure`] to free its slot.
+    pub fn admit(&self, max_trials: u64) -> Option<Admission> {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.state {
+            BreakerState::Closed => Some(Admission::Regular),
+            BreakerState::Open => None,
+            BreakerState::HalfOpen if inner.trials_in_flight < max_trials => {
+                inner.trials_in_flight += 1;
+                Some(Admission::Trial(inner.half_open_period))
+            }
+            BreakerState::HalfOpen => None,
+        }
+    }
+
+    /// Reports a trial that reached the backend; enough successful trials close the breaker.
+    pub fn on_success(
+        &self,
+        admission: Admission,
+        successes_to_close: u64,
+    ) -> Option<BreakerState> {
+        let mut inner = self.inner.lock().unwrap();
+        if !Self::is_current_trial(&inner, admission) {
+            return None;
+        }
+        inner.trials_in_flight = inner.trials_in_flight.saturating_sub(1);
+        inner.trial_successes += 1;
+
+        if inner.trial_successes >= successes_to_close {
+            inner.state = BreakerState::Closed;
+            return Some(BreakerState::Closed);
+        }
+        None
+    }
+
+    /// Reports a trial that could not reach the backend; any failed trial re-opens the breaker.
+    pub fn on_failure(&self, admission: Admission) -> Option<BreakerState> {
+        let mut inner = self.inner.lock().unwrap();
+        if !Self::is_current_trial(&inner, admission) {
+            return None;
+        }
+        Self::open(&mut inner);
+        Some(BreakerState::Open)
+    }
+
+    /// Opens the breaker, e.g. after a failed health check or an error-rate ejection.
+    pub fn trip(&self) -> Option<BreakerState> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state == BreakerState::Open {
+            return None;
+        }
+        Self::open(&mut inner);
+        Some(BreakerState::Open)
+    }
+
+    /// Reports a passing health check; an open breaker past its cool-down moves to half-open.
+    pub fn on_probe_success(&self, cool_down: Duration) -> Option<BreakerState> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != BreakerState::Open || inner.opened_at.elapsed() < cool_down {
+            return None;
+        }
+        inner.state = BreakerState::HalfOpen;
+        inner.half_open_period += 1;
+        inner.trials_in_flight = 0;
+        inner.trial_successes = 0;
+        Some(BreakerState::HalfOpen)
+    }
+
+    /// Outcomes of regular connections and of trials granted before the breaker last left
+    /// half-open are ignored.
+    fn is_current_trial(inner: &BreakerInner, admission: Admission) -> bool {
+        inner.state == BreakerState::HalfOpen
+            && admission == Admission::Trial(inner.half_open_period)
+    }
+
+    fn open(inner: &mut BreakerInner) {
+        inner.state = BreakerState::Open;
+        inner.opened_at = Instant::now();
+        inner.trials_in_flight = 0;
+        inner.trial_successes = 0;
+    }
+}
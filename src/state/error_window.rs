@@ -0,0 +1,32 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Outcomes of proxied connections seen within a sliding time window.
+#[derive(Debug, Default)]
+pub struct ErrorWindow {
+    samples: Mutex<VecDeque<(Instant, bool)>>,
+}
+
+impl ErrorWindow {
+    /// Records one outcome and returns `(failures, total)` over the last `window`.
+    pub fn record(&self, success: bool, window: Duration) -> (u64, u64) {
+        let now = Instant::now();
+        let mut samples = self.samples.lock().unwrap();
+
+        samples.push_back((now, success));
+        while samples
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > window)
+        {
+            samples.pop_front();
+        }
+
+        let failures = samples.iter().filter(|(_, ok)| !ok).count() as u64;
+        (failures, samples.len() as u64)
+    }
+
+    pub fn reset(&self) {
+        self.samples.lock().unwrap().clear();
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Peak-sensitive moving average of the latency seen on proxied connections.
+#[derive(Debug, Default)]
+pub struct LatencyEstimate {
+    estimate: Mutex<Option<(f64, Instant)>>,
+}
+
+impl LatencyEstimate {
+    /// Folds in one measured latency. A sample above the estimate replaces it outright so
+    /// slowdowns show at once; lower samples are averaged in, weighted by how long the
+    /// estimate has gone without an update relative to `decay`.
+    pub fn observe(&self, latency: Duration, decay: Duration) {
+        let now = Instant::now();
+        let sample = latency.as_secs_f64() * 1000.0;
+        let mut estimate = self.estimate.lock().unwrap();
+
+        let next = match *estimate {
+            Some((curr, _)) if sample >= curr => sample,
+            Some((curr, at)) => {
+                let keep = Self::retention(now.duration_since(at), decay);
+                curr * keep + sample * (1.0 - keep)
+            }
+            None => sample,
+        };
+        *estimate = Some((next, now));
+    }
+
+    /// The estimate in milliseconds decayed towards 0 for the time since the last sample, so an
+    /// idle backend is eventually retried. `None` until the first sample.
+    pub fn estimate_ms(&self, decay: Duration) -> Option<f64> {
+        self.estimate
+            .lock()
+            .unwrap()
+            .map(|(curr, at)| curr * Self::retention(at.elapsed(), decay))
+    }
+
+    fn retention(elapsed: Duration, decay: Duration) -> f64 {
+        if decay.is_zero() {
+            return 0.0;
+        }
+        (-elapsed.as_secs_f64() / decay.as_secs_f64()).exp()
+    }
+}
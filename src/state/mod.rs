@@ -1 +1,5 @@
 pub mod backend;
+pub mod backend_state;
+pub mod circuit_breaker;
+pub mod error_window;
+pub mod latency_estimate;
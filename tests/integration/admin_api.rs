@@ -0,0 +1,217 @@
+use super::support::{Pool, Process, backend, echo_server, free_port, pool};
+use monad_load_balancer::admin::admin_server::AdminServer;
+use monad_load_balancer::state::backend_state::BackendState;
+use serde_json::Value;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio::time::{sleep, timeout};
+
+/// Serves the admin API over `pool` on a free port and returns its address.
+async fn admin(pool: Pool) -> String {
+    let addr = format!("127.0.0.1:{}", free_port());
+    let cfg = serde_yaml::from_str(&format!("address: \"{}\"", addr)).unwrap();
+    let (tx, mut rx) = mpsc::channel(1024);
+    tokio::spawn(async move { while rx.recv().await.is_some() {} });
+    let server = AdminServer::new(cfg, pool, tx);
+    tokio::spawn(async move { server.serve().await });
+
+    while TcpStream::connect(&addr).await.is_err() {
+        sleep(Duration::from_millis(10)).await;
+    }
+    addr
+}
+
+/// Sends one request and returns the status code and the JSON body.
+async fn request(addr: &str, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let req = format!(
+        "{} {} HTTP/1.1\r\nHost: admin\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    );
+    stream.write_all(req.as_bytes()).await.unwrap();
+
+    let mut res = String::new();
+    timeout(Duration::from_secs(5), stream.read_to_string(&mut res))
+        .await
+        .unwrap()
+        .unwrap();
+    let status = res[9..12].parse().unwrap();
+    let body = res.split_once("\r\n\r\n").unwrap().1;
+    (status, serde_json::from_str(body).unwrap())
+}
+
+fn two_backends() -> Pool {
+    pool(vec![
+        backend("srv-01", "127.0.0.1:9001"),
+        backend("srv-02", "127.0.0.1:9002"),
+    ])
+}
+
+#[tokio::test]
+async fn lists_and_shows_backends() {
+    let addr = admin(two_backends()).await;
+
+    let (status, body) = request(&addr, "GET", "/backends", "").await;
+    assert_eq!(status, 200);
+    let ids: Vec<_> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, ["srv-01", "srv-02"]);
+
+    let (status, body) = request(&addr, "GET", "/backends/srv-02", "").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["address"], "127.0.0.1:9002");
+    assert_eq!(body["base_weight"], 10);
+
+    let (status, _) = request(&addr, "GET", "/backends/srv-09", "").await;
+    assert_eq!(status, 404);
+    let (status, _) = request(&addr, "PATCH", "/backends/srv-01", "").await;
+    assert_eq!(status, 405);
+    let (status, _) = request(&addr, "GET", "/metrics", "").await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn sets_weight_and_state() {
+    let pool = two_backends();
+    let addr = admin(pool.clone()).await;
+
+    let (status, body) = request(&addr, "PUT", "/backends/srv-01/weight", r#"{"weight": 3}"#).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["current_weight"], 3);
+
+    let (status, _) = request(
+        &addr,
+        "PUT",
+        "/backends/srv-01/state",
+        r#"{"state": "drain"}"#,
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let backends = pool.read().await;
+    assert_eq!(backends[0].base_weight.load(Ordering::Relaxed), 3);
+    assert_eq!(backends[0].current_weight.load(Ordering::Relaxed), 3);
+    assert_eq!(backends[0].state(), BackendState::Draining);
+    drop(backends);
+
+    let (status, _) = request(&addr, "PUT", "/backends/srv-01/weight", r#"{"weight": -1}"#).await;
+    assert_eq!(status, 400);
+    let (status, _) = request(
+        &addr,
+        "PUT",
+        "/backends/srv-01/state",
+        r#"{"state": "gone"}"#,
+    )
+    .await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn adds_and_removes_backends() {
+    let pool = two_backends();
+    let addr = admin(pool.clone()).await;
+
+    let new =
+        r#"{"id": "srv-03", "address": "127.0.0.1:9003", "max_connections": 10, "weight": 5}"#;
+    let (status, body) = request(&addr, "POST", "/backends", new).await;
+    assert_eq!(status, 201);
+    assert_eq!(body["id"], "srv-03");
+
+    let (status, _) = request(&addr, "DELETE", "/backends/srv-01", "").await;
+    assert_eq!(status, 200);
+    let (status, _) = request(&addr, "DELETE", "/backends/srv-01", "").await;
+    assert_eq!(status, 404);
+
+    let ids: Vec<_> = pool.read().await.iter().map(|b| b.id.clone()).collect();
+    assert_eq!(ids, ["srv-02", "srv-03"]);
+}
+
+#[tokio::test]
+async fn validates_added_backends() {
+    let pool = two_backends();
+    let addr = admin(pool.clone()).await;
+
+    let invalid = r#"{"id": "", "address": "nowhere", "max_connections": 0, "weight": 5}"#;
+    let (status, body) = request(&addr, "POST", "/backends", invalid).await;
+    assert_eq!(status, 400);
+    let errors: Vec<_> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e.as_str().unwrap().split(':').next().unwrap().to_string())
+        .collect();
+    assert_eq!(errors, ["body.id", "body.address", "body.max_connections"]);
+
+    let same_id =
+        r#"{"id": "srv-01", "address": "127.0.0.1:9009", "max_connections": 10, "weight": 5}"#;
+    let (status, _) = request(&addr, "POST", "/backends", same_id).await;
+    assert_eq!(status, 409);
+    let same_address =
+        r#"{"id": "srv-09", "address": "127.0.0.1:9001", "max_connections": 10, "weight": 5}"#;
+    let (status, _) = request(&addr, "POST", "/backends", same_address).await;
+    assert_eq!(status, 409);
+
+    assert_eq!(pool.read().await.len(), 2);
+}
+
+#[tokio::test]
+async fn rejects_malformed_requests() {
+    let addr = admin(two_backends()).await;
+
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    stream.write_all(b"nonsense\r\n\r\n").await.unwrap();
+    let mut res = String::new();
+    stream.read_to_string(&mut res).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 400"), "{}", res);
+
+    let (status, _) = request(&addr, "POST", "/backends", "{not json").await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn an_idle_admin_connection_does_not_hold_up_shutdown() {
+    let port = free_port();
+    let admin_port = free_port();
+    let backend = echo_server().await;
+    let config = format!(
+        r#"
+balancer:
+  algorithm: "round_robin"
+  check_interval_ms: 60000
+  port: {}
+  drain_timeout_ms: 1000
+backends:
+  - id: "srv-01"
+    address: "{}"
+    max_connections: 10
+    weight: 10
+thresholds:
+  latency_critical_ms: 1000
+  error_rate_limit: 0.5
+  recovery_step: 1
+admin:
+  address: "127.0.0.1:{}"
+"#,
+        port, backend, admin_port
+    );
+    let mut lb = Process::start("admin-idle", &config, port).await;
+
+    let _idle = TcpStream::connect(("127.0.0.1", admin_port)).await.unwrap();
+    sleep(Duration::from_millis(100)).await;
+    lb.terminate();
+
+    assert!(
+        lb.exited_within(Duration::from_secs(3)).await.is_some(),
+        "an open admin connection kept the balancer running"
+    );
+}
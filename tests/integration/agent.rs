@@ -0,0 +1,44 @@
+use super::support::closed_addr;
+use monad_load_balancer::health::agent::{AgentCommand, query};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// Starts an agent that answers with `reply`, after reading a line when `expects_line` is set.
+async fn agent(reply: &'static str, expects_line: bool) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                if expects_line {
+                    let mut line = String::new();
+                    let _ = stream.read_line(&mut line).await;
+                }
+                let _ = stream.get_mut().write_all(reply.as_bytes()).await;
+            });
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn reads_the_first_reply_line() {
+    let addr = agent("50% drain\nignored\n", false).await;
+    let report = query(&addr, None).await.unwrap();
+    assert_eq!(report.weight_pct, Some(50));
+    assert_eq!(report.command, Some(AgentCommand::Drain));
+}
+
+#[tokio::test]
+async fn writes_send_before_reading() {
+    let addr = agent("up\n", true).await;
+    let report = query(&addr, Some("status\n")).await.unwrap();
+    assert_eq!(report.command, Some(AgentCommand::Up));
+}
+
+#[tokio::test]
+async fn fails_without_a_reply() {
+    assert!(query(&agent("", false).await, None).await.is_err());
+    assert!(query(&closed_addr(), None).await.is_err());
+}
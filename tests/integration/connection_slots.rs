@@ -0,0 +1,43 @@
+use super::support::{THRESHOLDS, backend, balancer, echo_server, pool, proxied, roundtrip};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+const RETRY: &str = "max_attempts: 5\ninitial_delay_ms: 50\nbackoff_factor: 1.0\nmax_delay_ms: 50\njitter: 0.0\ndeadline_ms: 0";
+
+#[tokio::test]
+async fn a_finished_connection_releases_its_slot() {
+    let echo = backend("echo", &echo_server().await);
+    let (balancer, _rx) = balancer(pool(vec![echo.clone()]), RETRY, THRESHOLDS);
+
+    let (mut client, routed) = proxied(&balancer).await;
+    assert_eq!(roundtrip(&mut client, "ping").await, "ping");
+    assert_eq!(echo.active_conn.load(Ordering::Relaxed), 1);
+
+    // Closing the client's side must end both directions, not just the one it was writing.
+    drop(client);
+    timeout(Duration::from_secs(5), routed)
+        .await
+        .expect("connection still open after the client closed")
+        .unwrap()
+        .unwrap();
+    assert_eq!(echo.active_conn.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn a_full_backend_is_retried_after_backoff() {
+    let echo = backend("echo", &echo_server().await);
+    echo.max_conn.store(1, Ordering::Relaxed);
+    let (balancer, _rx) = balancer(pool(vec![echo.clone()]), RETRY, THRESHOLDS);
+
+    let (mut first, first_routed) = proxied(&balancer).await;
+    assert_eq!(roundtrip(&mut first, "one").await, "one");
+
+    let (mut second, _second_routed) = proxied(&balancer).await;
+    sleep(Duration::from_millis(20)).await;
+    drop(first);
+    first_routed.await.unwrap().unwrap();
+
+    assert_eq!(roundtrip(&mut second, "two").await, "two");
+    assert_eq!(echo.active_conn.load(Ordering::Relaxed), 1);
+}
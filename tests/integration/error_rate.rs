@@ -0,0 +1,62 @@
+use super::support::{backend, balancer, echo_server, events, pool, proxied, reset, roundtrip};
+use monad_load_balancer::logging::events::exporter_event::ExporterEvent;
+use std::sync::atomic::Ordering;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const RETRY: &str = "max_attempts: 1";
+const THRESHOLDS: &str =
+    "latency_critical_ms: 200\nerror_rate_limit: 0.5\nrecovery_step: 1\nerror_min_samples: 4";
+
+/// Starts a server that resets every connection as soon as it has read something.
+async fn resetting_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = [0; 16];
+            let _ = stream.read(&mut buf).await;
+            reset(stream);
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn backend_resets_eject_the_backend() {
+    let flaky = backend("flaky", &resetting_server().await);
+    let (balancer, mut rx) = balancer(pool(vec![flaky.clone()]), RETRY, THRESHOLDS);
+
+    for _ in 0..4 {
+        let (mut client, routed) = proxied(&balancer).await;
+        client.write_all(b"ping").await.unwrap();
+        let _ = routed.await.unwrap();
+    }
+
+    assert_eq!(flaky.current_weight.load(Ordering::Relaxed), 0);
+    assert!(
+        events(&mut rx)
+            .iter()
+            .any(|e| matches!(e, ExporterEvent::BackendEjected { .. }))
+    );
+}
+
+#[tokio::test]
+async fn client_resets_do_not_count_against_the_backend() {
+    let healthy = backend("healthy", &echo_server().await);
+    let (balancer, mut rx) = balancer(pool(vec![healthy.clone()]), RETRY, THRESHOLDS);
+
+    for _ in 0..8 {
+        let (mut client, routed) = proxied(&balancer).await;
+        assert_eq!(roundtrip(&mut client, "ping").await, "ping");
+        reset(client);
+        let _ = routed.await.unwrap();
+    }
+
+    assert_eq!(healthy.current_weight.load(Ordering::Relaxed), 10);
+    assert!(
+        !events(&mut rx)
+            .iter()
+            .any(|e| matches!(e, ExporterEvent::BackendEjected { .. }))
+    );
+}
@@ -0,0 +1,76 @@
+use super::support::{
+    THRESHOLDS, backend, balancer, closed_addr, echo_server, events, pool, proxied, roundtrip,
+    serve_echo,
+};
+use monad_load_balancer::logging::events::exporter_event::ExporterEvent;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::time::sleep;
+
+const RETRY: &str = "max_attempts: 3\ninitial_delay_ms: 10\nbackoff_factor: 1.0\nmax_delay_ms: 10\njitter: 0.0\ndeadline_ms: 0";
+
+#[tokio::test]
+async fn fails_over_to_the_next_backend() {
+    let dead = closed_addr();
+    let live = echo_server().await;
+    let pool = pool(vec![backend("dead", &dead), backend("live", &live)]);
+    let (balancer, mut rx) = balancer(pool, RETRY, THRESHOLDS);
+
+    // Round robin starts with the dead backend for the first client.
+    let (mut client, _routed) = proxied(&balancer).await;
+    assert_eq!(roundtrip(&mut client, "hello").await, "hello");
+
+    let failed: Vec<_> = events(&mut rx)
+        .into_iter()
+        .filter_map(|e| match e {
+            ExporterEvent::ConnectFailed { addr, attempt, .. } => Some((addr, attempt)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(failed, vec![(dead, 1)]);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let pool = pool(vec![
+        backend("dead-1", &closed_addr()),
+        backend("dead-2", &closed_addr()),
+    ]);
+    let (balancer, mut rx) = balancer(pool, RETRY, THRESHOLDS);
+
+    let (_client, routed) = proxied(&balancer).await;
+    assert!(routed.await.unwrap().is_err());
+
+    let attempts = events(&mut rx)
+        .iter()
+        .filter(|e| matches!(e, ExporterEvent::ConnectFailed { .. }))
+        .count();
+    assert_eq!(attempts, 3);
+}
+
+#[tokio::test]
+async fn retries_a_backend_that_comes_back() {
+    let addr = closed_addr();
+    let pool = pool(vec![backend("restarting", &addr)]);
+    let retry = "max_attempts: 5\ninitial_delay_ms: 100\nbackoff_factor: 1.0\nmax_delay_ms: 100\njitter: 0.0\ndeadline_ms: 0";
+    let (balancer, _rx) = balancer(pool, retry, THRESHOLDS);
+
+    let (mut client, _routed) = proxied(&balancer).await;
+    // The only backend refuses the first attempt and is listening again before the second.
+    sleep(Duration::from_millis(50)).await;
+    tokio::spawn(serve_echo(TcpListener::bind(&addr).await.unwrap()));
+
+    assert_eq!(roundtrip(&mut client, "back").await, "back");
+}
+
+#[tokio::test]
+async fn stops_at_the_retry_deadline() {
+    let pool = pool(vec![backend("dead", &closed_addr())]);
+    let retry = "max_attempts: 100\ninitial_delay_ms: 50\nbackoff_factor: 1.0\nmax_delay_ms: 50\njitter: 0.0\ndeadline_ms: 300";
+    let (balancer, _rx) = balancer(pool, retry, THRESHOLDS);
+
+    let started = tokio::time::Instant::now();
+    let (_client, routed) = proxied(&balancer).await;
+    assert!(routed.await.unwrap().is_err());
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,79 @@
+use monad_load_balancer::config::health_check_cfg::GrpcCheckCfg;
+use monad_load_balancer::health::checks::grpc_check::GrpcCheck;
+use monad_load_balancer::health::traits::health_check::HealthCheck;
+use tonic::transport::Server;
+use tonic::transport::server::TcpIncoming;
+use tonic_health::ServingStatus;
+use tonic_health::server::HealthReporter;
+
+/// Starts a `grpc.health.v1` server on a free local port and returns its reporter and address.
+async fn health_server() -> (HealthReporter, String) {
+    let (reporter, service) = tonic_health::server::health_reporter();
+    let incoming = TcpIncoming::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let addr = incoming.local_addr().unwrap().to_string();
+    tokio::spawn(
+        Server::builder()
+            .add_service(service)
+            .serve_with_incoming(incoming),
+    );
+    (reporter, addr)
+}
+
+fn check(service: &str) -> GrpcCheck {
+    GrpcCheck::new(GrpcCheckCfg {
+        service: service.to_string(),
+        timeout_ms: None,
+    })
+}
+
+#[tokio::test]
+async fn passes_only_while_the_named_service_is_serving() {
+    let (reporter, addr) = health_server().await;
+    let orders = check("orders");
+
+    reporter
+        .set_service_status("orders", ServingStatus::Serving)
+        .await;
+    assert!(orders.check(&addr).await.is_ok());
+
+    reporter
+        .set_service_status("orders", ServingStatus::NotServing)
+        .await;
+    let err = orders.check(&addr).await.unwrap_err();
+    assert!(err.to_string().contains("NOT_SERVING"), "{}", err);
+
+    reporter
+        .set_service_status("orders", ServingStatus::Serving)
+        .await;
+    assert!(orders.check(&addr).await.is_ok());
+}
+
+#[tokio::test]
+async fn empty_service_reports_the_whole_server() {
+    let (reporter, addr) = health_server().await;
+    let server = check("");
+
+    assert!(server.check(&addr).await.is_ok());
+
+    reporter
+        .set_service_status("", ServingStatus::NotServing)
+        .await;
+    assert!(server.check(&addr).await.is_err());
+}
+
+#[tokio::test]
+async fn unknown_service_fails() {
+    let (_reporter, addr) = health_server().await;
+
+    assert!(check("payments").check(&addr).await.is_err());
+}
+
+#[tokio::test]
+async fn unreachable_server_fails() {
+    let addr = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().to_string()
+    };
+
+    assert!(check("").check(&addr).await.is_err());
+}
@@ -0,0 +1,138 @@
+use super::support::{closed_addr, echo_server};
+use monad_load_balancer::health::checks::exec_check::ExecCheck;
+use monad_load_balancer::health::checks::http_check::HttpCheck;
+use monad_load_balancer::health::checks::send_expect_check::SendExpectCheck;
+use monad_load_balancer::health::checks::tcp_check::TcpCheck;
+use monad_load_balancer::health::traits::health_check::HealthCheck;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Starts a server that answers every connection with `reply` once it has read something, or
+/// right away when `greet` is set.
+async fn reply_server(reply: &'static str, greet: bool) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                if !greet {
+                    let mut buf = [0; 1024];
+                    let _ = stream.read(&mut buf).await;
+                }
+                let _ = stream.write_all(reply.as_bytes()).await;
+            });
+        }
+    });
+    addr
+}
+
+fn http(yaml: &str) -> HttpCheck {
+    HttpCheck::new(serde_yaml::from_str(yaml).unwrap())
+}
+
+const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 16\r\n\r\n{\"status\":\"up\"}\n";
+const UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n";
+const NO_CONTENT: &str = "HTTP/1.1 204 No Content\r\n\r\n";
+
+#[tokio::test]
+async fn http_check_matches_the_status() {
+    let ok = reply_server(OK, false).await;
+    let unavailable = reply_server(UNAVAILABLE, false).await;
+    let no_content = reply_server(NO_CONTENT, false).await;
+
+    let default = http("path: /health");
+    assert!(default.check(&ok).await.is_ok());
+    assert!(default.check(&unavailable).await.is_err());
+
+    let only_204 = http("path: /health\nexpected_status: [204]");
+    assert!(only_204.check(&no_content).await.is_ok());
+    assert!(only_204.check(&ok).await.is_err());
+
+    let any_5xx = http("expected_status: [\"500-599\"]");
+    assert!(any_5xx.check(&unavailable).await.is_ok());
+}
+
+#[tokio::test]
+async fn http_check_matches_the_body() {
+    let ok = reply_server(OK, false).await;
+
+    assert!(http("body_contains: \"up\"").check(&ok).await.is_ok());
+    assert!(http("body_contains: \"down\"").check(&ok).await.is_err());
+    assert!(
+        http("body_regex: '\"status\":\\s*\"(up|ok)\"'")
+            .check(&ok)
+            .await
+            .is_ok()
+    );
+    assert!(http("body_regex: '^down'").check(&ok).await.is_err());
+}
+
+#[tokio::test]
+async fn http_check_fails_on_garbage_and_refused_connections() {
+    let garbage = reply_server("SSH-2.0-OpenSSH\r\n", false).await;
+    assert!(http("path: /").check(&garbage).await.is_err());
+    assert!(http("path: /").check(&closed_addr()).await.is_err());
+}
+
+fn send_expect(yaml: &str) -> SendExpectCheck {
+    SendExpectCheck::new(serde_yaml::from_str(yaml).unwrap())
+}
+
+#[tokio::test]
+async fn send_expect_check_waits_for_the_expected_reply() {
+    let redis = reply_server("+PONG\r\n", false).await;
+
+    assert!(
+        send_expect("send: \"PING\\r\\n\"\nexpect: \"+PONG\"")
+            .check(&redis)
+            .await
+            .is_ok()
+    );
+    // The server closes without ever sending the expected text.
+    assert!(
+        send_expect("send: \"PING\\r\\n\"\nexpect: \"+OK\"")
+            .check(&redis)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn send_expect_check_reads_banners() {
+    let smtp = reply_server("220 mail ready\r\n", true).await;
+
+    assert!(send_expect("expect: \"220\"").check(&smtp).await.is_ok());
+    // An empty `expect` passes on any reply.
+    assert!(send_expect("expect: \"\"").check(&smtp).await.is_ok());
+    assert!(
+        send_expect("expect: \"\"")
+            .check(&closed_addr())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn exec_check_uses_the_exit_status() {
+    let exec = |script: &str| {
+        ExecCheck::new(
+            serde_yaml::from_str(&format!("command: sh\nargs: [\"-c\", {:?}]", script)).unwrap(),
+        )
+    };
+
+    assert!(exec("exit 0").check("127.0.0.1:9001").await.is_ok());
+    assert!(exec("exit 3").check("127.0.0.1:9001").await.is_err());
+    // The probed address is passed in the environment.
+    assert!(
+        exec("test \"$LB_BACKEND_HOST\" = 127.0.0.1 && test \"$LB_BACKEND_PORT\" = 9001")
+            .check("127.0.0.1:9001")
+            .await
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn tcp_check_connects() {
+    assert!(TcpCheck::new().check(&echo_server().await).await.is_ok());
+    assert!(TcpCheck::new().check(&closed_addr()).await.is_err());
+}
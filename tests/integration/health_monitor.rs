@@ -0,0 +1,106 @@
+use super::support::{backend, closed_addr, pool, serve_echo};
+use monad_load_balancer::health::health::Health;
+use monad_load_balancer::logging::events::exporter_event::ExporterEvent;
+use monad_load_balancer::state::backend::Backend;
+use monad_load_balancer::state::backend_state::BackendState;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc::{self, Receiver};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+
+const INTERVAL_MS: u64 = 100;
+
+/// Monitors `backend` every `INTERVAL_MS` with `rise: 2` and `fall: 3`.
+fn monitor(backend: Arc<Backend>) -> (JoinHandle<anyhow::Result<()>>, Receiver<ExporterEvent>) {
+    let thresholds = serde_yaml::from_str(
+        "latency_critical_ms: 1000\nerror_rate_limit: 0.5\nrecovery_step: 10\nrise: 2\nfall: 3",
+    )
+    .unwrap();
+    let balancer = serde_yaml::from_str(&format!(
+        "algorithm: round_robin\ncheck_interval_ms: {}\nport: 9090",
+        INTERVAL_MS
+    ))
+    .unwrap();
+    let (tx, rx) = mpsc::channel(1024);
+    let health = Arc::new(Health::new(thresholds, balancer, pool(vec![backend]), tx));
+    (tokio::spawn(async move { health.monitor().await }), rx)
+}
+
+/// Waits for the next `BackendDown` or `BackendUp` event.
+async fn next_edge(rx: &mut Receiver<ExporterEvent>) -> ExporterEvent {
+    loop {
+        let event = timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("no state change within 5s")
+            .unwrap();
+        if matches!(
+            event,
+            ExporterEvent::BackendDown { .. } | ExporterEvent::BackendUp { .. }
+        ) {
+            return event;
+        }
+    }
+}
+
+#[tokio::test]
+async fn goes_down_after_fall_failures_and_up_after_rise_successes() {
+    let addr = closed_addr();
+    let srv = backend("srv", &addr);
+    let (monitor, mut rx) = monitor(srv.clone());
+
+    assert!(matches!(
+        next_edge(&mut rx).await,
+        ExporterEvent::BackendDown { .. }
+    ));
+    assert_eq!(srv.consecutive_failures.load(Ordering::Relaxed), 3);
+    assert_eq!(srv.state(), BackendState::Down);
+    assert_eq!(srv.current_weight.load(Ordering::Relaxed), 0);
+
+    tokio::spawn(serve_echo(TcpListener::bind(&addr).await.unwrap()));
+    assert!(matches!(
+        next_edge(&mut rx).await,
+        ExporterEvent::BackendUp { .. }
+    ));
+    assert_eq!(srv.consecutive_successes.load(Ordering::Relaxed), 2);
+    assert!(srv.state().is_routable());
+
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn a_single_failed_probe_does_not_take_a_backend_down() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let serving = tokio::spawn(serve_echo(listener));
+    let srv = backend("srv", &addr);
+    let (monitor, _rx) = monitor(srv.clone());
+
+    // Down for about one interval: at most two probes fail, one short of `fall`.
+    sleep(Duration::from_millis(INTERVAL_MS / 2)).await;
+    serving.abort();
+    let _ = serving.await;
+    sleep(Duration::from_millis(INTERVAL_MS)).await;
+    tokio::spawn(serve_echo(TcpListener::bind(&addr).await.unwrap()));
+
+    sleep(Duration::from_millis(INTERVAL_MS * 4)).await;
+    assert_ne!(srv.state(), BackendState::Down);
+    monitor.abort();
+}
+
+#[tokio::test]
+async fn reports_each_transition_once() {
+    let srv = backend("srv", &closed_addr());
+    let (monitor, mut rx) = monitor(srv.clone());
+
+    // Many failed checks in a row still produce a single `BackendDown`.
+    sleep(Duration::from_millis(INTERVAL_MS * 10)).await;
+    monitor.abort();
+    let mut downs = 0;
+    while let Ok(event) = rx.try_recv() {
+        downs += matches!(event, ExporterEvent::BackendDown { .. }) as usize;
+    }
+    assert_eq!(downs, 1);
+}
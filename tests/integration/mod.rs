@@ -1 +1,11 @@
-
+mod admin_api;
+mod agent;
+mod connection_slots;
+mod error_rate;
+mod failover;
+mod grpc_check;
+mod health_checks;
+mod health_monitor;
+mod reload;
+mod shutdown;
+mod support;
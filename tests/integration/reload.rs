@@ -0,0 +1,248 @@
+use super::support::{Pool, backend};
+use clap::Parser;
+use monad_load_balancer::algorithms::factories::algorithm::Algorithm;
+use monad_load_balancer::balancer::balancer::Balancer;
+use monad_load_balancer::cli::cli_args::CliArgs;
+use monad_load_balancer::logging::events::exporter_event::ExporterEvent;
+use monad_load_balancer::reload::config_reloader::ConfigReloader;
+use monad_load_balancer::state::backend::Backend;
+use monad_load_balancer::state::backend_state::BackendState;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::sync::mpsc::{self, Receiver};
+use tokio::time::timeout;
+
+/// A config file in its own scratch directory, removed when dropped.
+struct ConfigFile {
+    dir: PathBuf,
+}
+
+impl ConfigFile {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("monad-lb-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self { dir }
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.join("config.yaml")
+    }
+
+    fn write(&self, backends: &[(&str, &str, u64)], extra: &str) {
+        std::fs::write(self.path(), config(backends, extra)).unwrap();
+    }
+}
+
+impl Drop for ConfigFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// A config listing `(id, address, weight)` backends, with `extra` YAML appended to each entry.
+fn config(backends: &[(&str, &str, u64)], extra: &str) -> String {
+    let mut yaml = String::from(
+        "balancer:\n  algorithm: \"round_robin\"\n  check_interval_ms: 60000\n  port: 9090\nbackends:\n",
+    );
+    for (id, address, weight) in backends {
+        yaml.push_str(&format!(
+            "  - id: \"{}\"\n    address: \"{}\"\n    max_connections: 10\n    weight: {}\n{}",
+            id, address, weight, extra
+        ));
+    }
+    yaml.push_str(
+        "thresholds:\n  latency_critical_ms: 1000\n  error_rate_limit: 0.5\n  recovery_step: 1\n",
+    );
+    yaml
+}
+
+async fn reloader(path: &Path, args: &[&str]) -> (ConfigReloader, Pool, Receiver<ExporterEvent>) {
+    let cli = CliArgs::parse_from(
+        ["monad_load_balancer", "--config", path.to_str().unwrap()]
+            .iter()
+            .chain(args),
+    );
+    let cfg = cli.load_cfg().await.unwrap();
+    let pool: Pool = Arc::new(RwLock::new(
+        cfg.backends
+            .iter()
+            .map(|b| Arc::new(Backend::from_cfg(b)))
+            .collect(),
+    ));
+    let (tx, rx) = mpsc::channel(1024);
+    let balancer = Balancer::new(
+        Algorithm::select(
+            cfg.balancer_cfg.algorithm.clone(),
+            &cfg.balancer_cfg.algorithm_options,
+        ),
+        pool.clone(),
+        cfg.balancer_cfg.retry.clone(),
+        cfg.thresholds_cfg.clone(),
+        tx.clone(),
+    );
+    let reloader = ConfigReloader::new(cli, cfg, pool.clone(), balancer, tx);
+    (reloader, pool, rx)
+}
+
+/// Reloads and returns the `ConfigReloaded` or `ConfigReloadFailed` event it produced.
+async fn reload(reloader: &ConfigReloader, rx: &mut Receiver<ExporterEvent>) -> ExporterEvent {
+    reloader.reload().await.unwrap();
+    loop {
+        let event = timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        if matches!(
+            event,
+            ExporterEvent::ConfigReloaded { .. } | ExporterEvent::ConfigReloadFailed { .. }
+        ) {
+            reloader.stop_health().await;
+            return event;
+        }
+    }
+}
+
+async fn ids(pool: &Pool) -> Vec<String> {
+    pool.read().await.iter().map(|b| b.id.clone()).collect()
+}
+
+#[tokio::test]
+async fn applies_added_removed_and_updated_backends() {
+    let file = ConfigFile::new("reload-diff");
+    file.write(
+        &[("a", "127.0.0.1:9001", 10), ("b", "127.0.0.1:9002", 10)],
+        "",
+    );
+    let (reloader, pool, mut rx) = reloader(&file.path(), &[]).await;
+    let b = pool.read().await[1].clone();
+
+    file.write(
+        &[("a", "127.0.0.1:9001", 4), ("c", "127.0.0.1:9003", 10)],
+        "",
+    );
+    let ExporterEvent::ConfigReloaded {
+        added,
+        removed,
+        updated,
+        ..
+    } = reload(&reloader, &mut rx).await
+    else {
+        panic!("reload failed");
+    };
+
+    assert_eq!(
+        (added, removed, updated),
+        (vec!["c".into()], vec!["b".into()], vec!["a".into()])
+    );
+    assert_eq!(ids(&pool).await, ["a", "c"]);
+    assert_eq!(pool.read().await[0].base_weight.load(Ordering::Relaxed), 4);
+    assert_eq!(b.state(), BackendState::Draining);
+}
+
+#[tokio::test]
+async fn reports_retry_overrides_as_updates() {
+    let file = ConfigFile::new("reload-retry");
+    file.write(&[("a", "127.0.0.1:9001", 10)], "");
+    let (reloader, pool, mut rx) = reloader(&file.path(), &[]).await;
+
+    file.write(
+        &[("a", "127.0.0.1:9001", 10)],
+        "    retry:\n      initial_delay_ms: 5\n",
+    );
+    let ExporterEvent::ConfigReloaded { updated, .. } = reload(&reloader, &mut rx).await else {
+        panic!("reload failed");
+    };
+
+    assert_eq!(updated, ["a"]);
+    let retry = pool.read().await[0].retry.read().unwrap().clone();
+    assert_eq!(retry.unwrap().initial_delay_ms, Some(5));
+}
+
+#[tokio::test]
+async fn keeps_the_running_config_when_the_file_is_invalid() {
+    let file = ConfigFile::new("reload-invalid");
+    file.write(&[("a", "127.0.0.1:9001", 10)], "");
+    let (reloader, pool, mut rx) = reloader(&file.path(), &[]).await;
+
+    file.write(
+        &[("a", "127.0.0.1:9001", 10), ("a", "127.0.0.1:9002", 10)],
+        "",
+    );
+    assert!(matches!(
+        reload(&reloader, &mut rx).await,
+        ExporterEvent::ConfigReloadFailed { .. }
+    ));
+    assert_eq!(ids(&pool).await, ["a"]);
+}
+
+#[tokio::test]
+async fn keeps_changes_made_through_the_admin_api() {
+    let file = ConfigFile::new("reload-admin");
+    let listed = [("a", "127.0.0.1:9001", 10), ("b", "127.0.0.1:9002", 10)];
+    file.write(&listed, "");
+    let (reloader, pool, mut rx) = reloader(&file.path(), &[]).await;
+
+    {
+        let mut backends = pool.write().await;
+        backends[0].base_weight.store(3, Ordering::Relaxed);
+        backends.remove(1);
+        backends.push(backend("x", "127.0.0.1:9009"));
+    }
+
+    // Nothing in the file changed, so nothing the admin API did is undone.
+    let ExporterEvent::ConfigReloaded {
+        added,
+        removed,
+        updated,
+        ..
+    } = reload(&reloader, &mut rx).await
+    else {
+        panic!("reload failed");
+    };
+    assert!(added.is_empty() && removed.is_empty() && updated.is_empty());
+    assert_eq!(ids(&pool).await, ["a", "x"]);
+    assert_eq!(pool.read().await[0].base_weight.load(Ordering::Relaxed), 3);
+
+    // A weight changed in the file wins over the one set at runtime.
+    file.write(
+        &[("a", "127.0.0.1:9001", 6), ("b", "127.0.0.1:9002", 10)],
+        "",
+    );
+    reload(&reloader, &mut rx).await;
+    assert_eq!(pool.read().await[0].base_weight.load(Ordering::Relaxed), 6);
+
+    // A file entry claiming the admin-added backend's address takes its place.
+    file.write(
+        &[
+            ("a", "127.0.0.1:9001", 6),
+            ("b", "127.0.0.1:9002", 10),
+            ("y", "127.0.0.1:9009", 10),
+        ],
+        "",
+    );
+    let ExporterEvent::ConfigReloaded { added, removed, .. } = reload(&reloader, &mut rx).await
+    else {
+        panic!("reload failed");
+    };
+    assert_eq!((added, removed), (vec!["y".into()], vec!["x".into()]));
+    assert_eq!(ids(&pool).await, ["a", "y"]);
+}
+
+#[tokio::test]
+async fn command_line_overrides_apply_on_reload() {
+    let file = ConfigFile::new("reload-cli");
+    file.write(&[("a", "127.0.0.1:9001", 10)], "");
+    let (reloader, _pool, mut rx) = reloader(&file.path(), &["--port", "9191"]).await;
+
+    // Port 0 is invalid in the file but overridden on the command line, so the reload goes through.
+    let invalid_port = config(&[("a", "127.0.0.1:9001", 4)], "").replace("port: 9090", "port: 0");
+    std::fs::write(file.path(), invalid_port).unwrap();
+    assert!(matches!(
+        reload(&reloader, &mut rx).await,
+        ExporterEvent::ConfigReloaded { .. }
+    ));
+}
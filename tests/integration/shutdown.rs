@@ -0,0 +1,110 @@
+use super::support::{Process, free_port};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::sleep;
+
+/// Starts a backend that answers each request with `done` after `delay`, or never when `None`.
+async fn slow_server(delay: Option<Duration>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                // Connections that close without a request end at once.
+                let mut buf = [0; 16];
+                if !matches!(stream.read(&mut buf).await, Ok(n) if n > 0) {
+                    return;
+                }
+                match delay {
+                    Some(delay) => {
+                        sleep(delay).await;
+                        let _ = stream.write_all(b"done").await;
+                    }
+                    None => sleep(Duration::from_secs(60)).await,
+                }
+            });
+        }
+    });
+    addr
+}
+
+fn config(port: u16, backend: &str, drain_timeout_ms: u64) -> String {
+    format!(
+        r#"
+balancer:
+  algorithm: "round_robin"
+  check_interval_ms: 60000
+  port: {}
+  drain_timeout_ms: {}
+backends:
+  - id: "srv-01"
+    address: "{}"
+    max_connections: 10
+    weight: 10
+thresholds:
+  latency_critical_ms: 1000
+  error_rate_limit: 0.5
+  recovery_step: 1
+"#,
+        port, drain_timeout_ms, backend
+    )
+}
+
+#[tokio::test]
+async fn in_flight_connections_finish_before_exit() {
+    let port = free_port();
+    let backend = slow_server(Some(Duration::from_millis(500))).await;
+    let mut lb = Process::start("drain", &config(port, &backend, 5000), port).await;
+
+    let mut client = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    client.write_all(b"req").await.unwrap();
+    sleep(Duration::from_millis(100)).await;
+    lb.terminate();
+
+    // New connections are refused while the old one drains.
+    sleep(Duration::from_millis(100)).await;
+    assert!(TcpStream::connect(("127.0.0.1", port)).await.is_err());
+
+    let mut reply = [0; 4];
+    client.read_exact(&mut reply).await.unwrap();
+    assert_eq!(&reply, b"done");
+    drop(client);
+
+    let status = lb.exited_within(Duration::from_secs(5)).await;
+    assert!(status.is_some(), "balancer still running after draining");
+    let log = lb.log();
+    assert!(log.contains("event=ShutdownStarted active=1"), "{}", log);
+    assert!(
+        log.contains("event=ShutdownComplete drained=1 force_closed=0"),
+        "{}",
+        log
+    );
+}
+
+#[tokio::test]
+async fn connections_left_at_the_drain_timeout_are_closed() {
+    let port = free_port();
+    let backend = slow_server(None).await;
+    let mut lb = Process::start("force-close", &config(port, &backend, 300), port).await;
+
+    let mut client = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    client.write_all(b"req").await.unwrap();
+    sleep(Duration::from_millis(100)).await;
+    lb.terminate();
+
+    let status = lb.exited_within(Duration::from_secs(3)).await;
+    assert!(
+        status.is_some(),
+        "balancer did not force-close after the drain timeout"
+    );
+    let mut reply = Vec::new();
+    let _ = client.read_to_end(&mut reply).await;
+    assert!(reply.is_empty());
+    let log = lb.log();
+    assert!(
+        log.contains("event=ShutdownComplete drained=0 force_closed=1"),
+        "{}",
+        log
+    );
+}
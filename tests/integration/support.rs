@@ -0,0 +1,197 @@
+use monad_load_balancer::algorithms::factories::algorithm::Algorithm;
+use monad_load_balancer::balancer::balancer::Balancer;
+use monad_load_balancer::config::algorithm_cfg::{AlgorithmOptionsCfg, AlgorithmType};
+use monad_load_balancer::config::backend_cfg::BackendCfg;
+use monad_load_balancer::logging::events::exporter_event::ExporterEvent;
+use monad_load_balancer::state::backend::Backend;
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::RwLock;
+use tokio::sync::mpsc::{self, Receiver};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+pub type Pool = Arc<RwLock<Vec<Arc<Backend>>>>;
+
+/// Starts a server on a free local port that echoes every connection back, returning its address.
+pub async fn echo_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(serve_echo(listener));
+    addr
+}
+
+pub async fn serve_echo(listener: TcpListener) {
+    while let Ok((mut stream, _)) = listener.accept().await {
+        tokio::spawn(async move {
+            let (mut reader, mut writer) = stream.split();
+            let _ = tokio::io::copy(&mut reader, &mut writer).await;
+        });
+    }
+}
+
+/// A local address nothing listens on, so connecting to it is refused.
+pub fn closed_addr() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+pub fn backend(id: &str, addr: &str) -> Arc<Backend> {
+    let cfg: BackendCfg = serde_yaml::from_str(&format!(
+        "id: {}\naddress: \"{}\"\nmax_connections: 100\nweight: 10",
+        id, addr
+    ))
+    .unwrap();
+    Arc::new(Backend::from_cfg(&cfg))
+}
+
+pub fn pool(backends: Vec<Arc<Backend>>) -> Pool {
+    Arc::new(RwLock::new(backends))
+}
+
+/// A round-robin balancer over `pool` using the given `retry` and `thresholds` YAML.
+pub fn balancer(pool: Pool, retry: &str, thresholds: &str) -> (Balancer, Receiver<ExporterEvent>) {
+    let (tx, rx) = mpsc::channel(1024);
+    let balancer = Balancer::new(
+        Algorithm::select(AlgorithmType::RoundRobin, &AlgorithmOptionsCfg::default()),
+        pool,
+        serde_yaml::from_str(retry).unwrap(),
+        serde_yaml::from_str(thresholds).unwrap(),
+        tx,
+    );
+    (balancer, rx)
+}
+
+pub const THRESHOLDS: &str = "latency_critical_ms: 200\nerror_rate_limit: 0.5\nrecovery_step: 1";
+
+/// Connects a client and hands the accepted end to `balancer`, returning the client end and the
+/// routing task.
+pub async fn proxied(balancer: &Balancer) -> (TcpStream, JoinHandle<anyhow::Result<()>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    let (accepted, _) = listener.accept().await.unwrap();
+
+    let balancer = balancer.clone();
+    let routed = tokio::spawn(async move { balancer.route_connection(accepted).await });
+    (client, routed)
+}
+
+/// Writes `msg` and reads the same number of bytes back.
+pub async fn roundtrip(client: &mut TcpStream, msg: &str) -> String {
+    client.write_all(msg.as_bytes()).await.unwrap();
+    let mut buf = vec![0; msg.len()];
+    timeout(Duration::from_secs(5), client.read_exact(&mut buf))
+        .await
+        .expect("no reply within 5s")
+        .unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Drains the events received so far.
+pub fn events(rx: &mut Receiver<ExporterEvent>) -> Vec<ExporterEvent> {
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+/// Closes `stream` with a TCP reset instead of a FIN.
+// A zero linger never blocks on drop, which is what the deprecation warns about.
+#[allow(deprecated)]
+pub fn reset(stream: TcpStream) {
+    stream.set_linger(Some(Duration::ZERO)).unwrap();
+}
+
+static NEXT_PORT: AtomicU16 = AtomicU16::new(0);
+
+/// A free local port below the ephemeral range, so no outgoing connection is handed it between
+/// the check and the balancer binding it.
+pub fn free_port() -> u16 {
+    loop {
+        let offset =
+            (std::process::id() as u16).wrapping_add(NEXT_PORT.fetch_add(1, Ordering::Relaxed));
+        let port = 20_000 + offset % 12_000;
+        if std::net::TcpListener::bind(("127.0.0.1", port)).is_ok() {
+            return port;
+        }
+    }
+}
+
+/// The balancer binary running from a scratch directory that holds its `config.yaml` and `log.txt`.
+pub struct Process {
+    child: Child,
+    dir: PathBuf,
+}
+
+impl Process {
+    /// Starts the binary with `config` and waits until it accepts connections on `port`.
+    pub async fn start(name: &str, config: &str, port: u16) -> Self {
+        let dir = std::env::temp_dir().join(format!("monad-lb-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("config.yaml"), config).unwrap();
+
+        let child = Command::new(env!("CARGO_BIN_EXE_monad_load_balancer"))
+            .current_dir(&dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let process = Self { child, dir };
+
+        let started = Instant::now();
+        loop {
+            match TcpStream::connect(("127.0.0.1", port)).await {
+                // Reset so the probe ends at once instead of being proxied like a client.
+                Ok(probe) => break reset(probe),
+                Err(_) => assert!(
+                    started.elapsed() < Duration::from_secs(10),
+                    "balancer did not start"
+                ),
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        process
+    }
+
+    /// Sends SIGTERM.
+    pub fn terminate(&self) {
+        let status = Command::new("kill")
+            .args(["-TERM", &self.child.id().to_string()])
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// Waits up to `limit` for the process to exit.
+    pub async fn exited_within(&mut self, limit: Duration) -> Option<ExitStatus> {
+        let started = Instant::now();
+        while started.elapsed() < limit {
+            if let Some(status) = self.child.try_wait().unwrap() {
+                return Some(status);
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        None
+    }
+
+    pub fn log(&self) -> String {
+        std::fs::read_to_string(self.dir.join("log.txt")).unwrap_or_default()
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
@@ -0,0 +1,49 @@
+use monad_load_balancer::health::agent::{AgentCommand, AgentReport};
+
+#[test]
+fn parses_weight_with_prefix() {
+    let report = AgentReport::parse("weight=40%");
+    assert_eq!(report.weight_pct, Some(40));
+    assert_eq!(report.command, None);
+}
+
+#[test]
+fn parses_bare_weight_and_command() {
+    let report = AgentReport::parse("75% up");
+    assert_eq!(report.weight_pct, Some(75));
+    assert_eq!(report.command, Some(AgentCommand::Up));
+}
+
+#[test]
+fn parses_commands_case_insensitively() {
+    assert_eq!(
+        AgentReport::parse("DRAIN").command,
+        Some(AgentCommand::Drain)
+    );
+    assert_eq!(
+        AgentReport::parse("maint").command,
+        Some(AgentCommand::Maint)
+    );
+    assert_eq!(AgentReport::parse("ready").command, Some(AgentCommand::Up));
+}
+
+#[test]
+fn accepts_comma_separated_tokens() {
+    let report = AgentReport::parse("drain,weight=10%");
+    assert_eq!(report.weight_pct, Some(10));
+    assert_eq!(report.command, Some(AgentCommand::Drain));
+}
+
+#[test]
+fn caps_weight_at_100_percent() {
+    assert_eq!(AgentReport::parse("250%").weight_pct, Some(100));
+}
+
+#[test]
+fn ignores_unknown_and_malformed_tokens() {
+    let report = AgentReport::parse("load=0.3 abc% stopped up");
+    assert_eq!(report.weight_pct, None);
+    assert_eq!(report.command, Some(AgentCommand::Up));
+
+    assert_eq!(AgentReport::parse(""), AgentReport::default());
+}
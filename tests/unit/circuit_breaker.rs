@@ -0,0 +1,91 @@
+use monad_load_balancer::state::circuit_breaker::{Admission, BreakerState, CircuitBreaker};
+use std::time::Duration;
+
+const TRIALS: u64 = 2;
+
+fn half_open() -> CircuitBreaker {
+    let breaker = CircuitBreaker::default();
+    breaker.trip();
+    assert_eq!(
+        breaker.on_probe_success(Duration::ZERO),
+        Some(BreakerState::HalfOpen)
+    );
+    breaker
+}
+
+#[test]
+fn closed_breaker_admits_regular_connections() {
+    let breaker = CircuitBreaker::default();
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    assert!(breaker.allows_traffic(TRIALS));
+    assert_eq!(breaker.admit(TRIALS), Some(Admission::Regular));
+    assert_eq!(breaker.on_success(Admission::Regular, TRIALS), None);
+    assert_eq!(breaker.on_failure(Admission::Regular), None);
+    assert_eq!(breaker.state(), BreakerState::Closed);
+}
+
+#[test]
+fn trip_opens_once() {
+    let breaker = CircuitBreaker::default();
+    assert_eq!(breaker.trip(), Some(BreakerState::Open));
+    assert_eq!(breaker.trip(), None);
+    assert!(!breaker.allows_traffic(TRIALS));
+    assert_eq!(breaker.admit(TRIALS), None);
+}
+
+#[test]
+fn stays_open_until_the_cool_down_elapses() {
+    let breaker = CircuitBreaker::default();
+    breaker.trip();
+    assert_eq!(breaker.on_probe_success(Duration::from_secs(60)), None);
+    assert_eq!(breaker.state(), BreakerState::Open);
+}
+
+#[test]
+fn half_open_limits_trials_in_flight() {
+    let breaker = half_open();
+    assert!(matches!(breaker.admit(TRIALS), Some(Admission::Trial(_))));
+    assert!(matches!(breaker.admit(TRIALS), Some(Admission::Trial(_))));
+    assert!(!breaker.allows_traffic(TRIALS));
+    assert_eq!(breaker.admit(TRIALS), None);
+}
+
+#[test]
+fn enough_successful_trials_close_it() {
+    let breaker = half_open();
+    let first = breaker.admit(TRIALS).unwrap();
+    let second = breaker.admit(TRIALS).unwrap();
+
+    assert_eq!(breaker.on_success(first, TRIALS), None);
+    // A settled trial frees its slot for another one.
+    assert!(breaker.allows_traffic(TRIALS));
+    assert_eq!(
+        breaker.on_success(second, TRIALS),
+        Some(BreakerState::Closed)
+    );
+    assert_eq!(breaker.state(), BreakerState::Closed);
+}
+
+#[test]
+fn a_failed_trial_reopens_it() {
+    let breaker = half_open();
+    let trial = breaker.admit(TRIALS).unwrap();
+    assert_eq!(breaker.on_failure(trial), Some(BreakerState::Open));
+    assert_eq!(breaker.state(), BreakerState::Open);
+}
+
+#[test]
+fn trials_from_an_earlier_half_open_period_are_ignored() {
+    let breaker = half_open();
+    let stale = breaker.admit(TRIALS).unwrap();
+    breaker.trip();
+    breaker.on_probe_success(Duration::ZERO);
+
+    // The late outcome neither counts towards closing nor frees a slot of the new period.
+    assert_eq!(breaker.on_success(stale, 1), None);
+    assert_eq!(breaker.on_failure(stale), None);
+    assert_eq!(breaker.state(), BreakerState::HalfOpen);
+
+    let fresh = breaker.admit(TRIALS).unwrap();
+    assert_eq!(breaker.on_success(fresh, 1), Some(BreakerState::Closed));
+}
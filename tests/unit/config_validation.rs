@@ -0,0 +1,113 @@
+use monad_load_balancer::config::load_balancer_cfg::LoadBalancerCfg;
+use monad_load_balancer::config::validation::ValidationErrors;
+
+const VALID: &str = r#"
+balancer:
+  algorithm: "least_conn"
+  check_interval_ms: 500
+  port: 9090
+backends:
+  - id: "srv-01"
+    address: "127.0.0.1:9001"
+    max_connections: 100
+    weight: 10
+  - id: "srv-02"
+    address: "127.0.0.1:9002"
+    max_connections: 100
+    weight: 10
+thresholds:
+  latency_critical_ms: 200
+  error_rate_limit: 0.05
+  recovery_step: 2
+"#;
+
+fn parse(yaml: &str) -> LoadBalancerCfg {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+/// The messages `validate` reported, each as `path: message`.
+fn problems(cfg: &LoadBalancerCfg) -> Vec<String> {
+    match cfg.validate() {
+        Ok(()) => Vec::new(),
+        Err(err) => err
+            .downcast_ref::<ValidationErrors>()
+            .expect("validate must fail with ValidationErrors")
+            .messages()
+            .to_vec(),
+    }
+}
+
+fn has_problem_at(problems: &[String], path: &str) -> bool {
+    let prefix = format!("{}: ", path);
+    problems.iter().any(|p| p.starts_with(&prefix))
+}
+
+#[test]
+fn accepts_a_valid_config() {
+    assert!(problems(&parse(VALID)).is_empty());
+}
+
+#[test]
+fn reports_every_problem_with_its_path() {
+    let mut cfg = parse(VALID);
+    cfg.balancer_cfg.port = 0;
+    cfg.balancer_cfg.check_interval_ms = 0;
+    cfg.backends[1].max_conn = 0;
+    cfg.backends[1].address = "not-an-address".into();
+
+    let problems = problems(&cfg);
+    assert_eq!(problems.len(), 4, "{:?}", problems);
+    assert!(has_problem_at(&problems, "balancer.port"));
+    assert!(has_problem_at(&problems, "balancer.check_interval_ms"));
+    assert!(has_problem_at(&problems, "backends[1].max_connections"));
+    assert!(has_problem_at(&problems, "backends[1].address"));
+}
+
+#[test]
+fn rejects_an_empty_backend_list() {
+    let mut cfg = parse(VALID);
+    cfg.backends.clear();
+    assert!(has_problem_at(&problems(&cfg), "backends"));
+}
+
+#[test]
+fn rejects_duplicate_ids_and_addresses() {
+    let mut cfg = parse(VALID);
+    cfg.backends[1].id = cfg.backends[0].id.clone();
+    cfg.backends[1].address = cfg.backends[0].address.clone();
+
+    let problems = problems(&cfg);
+    assert!(has_problem_at(&problems, "backends[1].id"));
+    assert!(has_problem_at(&problems, "backends[1].address"));
+    assert!(!has_problem_at(&problems, "backends[0].id"));
+}
+
+#[test]
+fn rejects_a_weight_below_the_recovery_step() {
+    let mut cfg = parse(VALID);
+    cfg.backends[0].weight = 1;
+    assert!(has_problem_at(&problems(&cfg), "backends[0].weight"));
+}
+
+#[test]
+fn rejects_out_of_range_thresholds() {
+    let mut cfg = parse(VALID);
+    cfg.thresholds_cfg.error_rate_limit = 1.5;
+    cfg.thresholds_cfg.recovery_step = 0;
+
+    let problems = problems(&cfg);
+    assert!(has_problem_at(&problems, "thresholds.error_rate_limit"));
+    assert!(has_problem_at(&problems, "thresholds.recovery_step"));
+}
+
+#[test]
+fn display_lists_every_problem() {
+    let mut cfg = parse(VALID);
+    cfg.balancer_cfg.port = 0;
+    cfg.backends.clear();
+
+    let text = cfg.validate().unwrap_err().to_string();
+    assert!(text.starts_with("2 configuration error(s)"), "{}", text);
+    assert!(text.contains("\n  - balancer.port: "), "{}", text);
+    assert!(text.contains("\n  - backends: "), "{}", text);
+}
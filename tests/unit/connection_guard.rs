@@ -0,0 +1,64 @@
+use monad_load_balancer::config::backend_cfg::BackendCfg;
+use monad_load_balancer::state::backend::Backend;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+fn backend(max_connections: u64) -> Arc<Backend> {
+    let cfg: BackendCfg = serde_yaml::from_str(&format!(
+        "id: srv-01\naddress: 127.0.0.1:9001\nmax_connections: {}\nweight: 10",
+        max_connections
+    ))
+    .unwrap();
+    Arc::new(Backend::from_cfg(&cfg))
+}
+
+#[test]
+fn reserves_up_to_max_connections() {
+    let backend = backend(2);
+    let first = backend.try_reserve();
+    let second = backend.try_reserve();
+    assert!(first.is_some() && second.is_some());
+    assert!(backend.try_reserve().is_none());
+    assert_eq!(backend.active_conn.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn dropping_the_guard_releases_the_slot() {
+    let backend = backend(1);
+    let guard = backend.try_reserve().unwrap();
+    assert!(backend.try_reserve().is_none());
+
+    drop(guard);
+    assert_eq!(backend.active_conn.load(Ordering::Relaxed), 0);
+    assert!(backend.try_reserve().is_some());
+}
+
+#[test]
+fn a_panic_releases_the_slot() {
+    let backend = backend(1);
+    let holder = backend.clone();
+    let _ = std::thread::spawn(move || {
+        let _guard = holder.try_reserve().unwrap();
+        panic!("connection task failed");
+    })
+    .join();
+    assert_eq!(backend.active_conn.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn concurrent_reservations_never_exceed_the_limit() {
+    let backend = backend(10);
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let backend = backend.clone();
+            std::thread::spawn(move || {
+                (0..1000)
+                    .filter_map(|_| backend.try_reserve())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    // Every guard is kept until all threads are done, so no slot is released in between.
+    let held: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+    assert_eq!(held.iter().map(Vec::len).sum::<usize>(), 10);
+}
@@ -0,0 +1,30 @@
+use monad_load_balancer::state::error_window::ErrorWindow;
+use std::thread::sleep;
+use std::time::Duration;
+
+const WINDOW: Duration = Duration::from_millis(50);
+
+#[test]
+fn counts_failures_within_the_window() {
+    let window = ErrorWindow::default();
+    assert_eq!(window.record(false, WINDOW), (1, 1));
+    assert_eq!(window.record(true, WINDOW), (1, 2));
+    assert_eq!(window.record(false, WINDOW), (2, 3));
+}
+
+#[test]
+fn old_samples_expire() {
+    let window = ErrorWindow::default();
+    window.record(false, WINDOW);
+    window.record(false, WINDOW);
+    sleep(WINDOW * 2);
+    assert_eq!(window.record(true, WINDOW), (0, 1));
+}
+
+#[test]
+fn reset_clears_every_sample() {
+    let window = ErrorWindow::default();
+    window.record(false, WINDOW);
+    window.reset();
+    assert_eq!(window.record(true, WINDOW), (0, 1));
+}
@@ -0,0 +1,184 @@
+use monad_load_balancer::algorithms::core::consistent_hash::ConsistentHash;
+use monad_load_balancer::algorithms::core::maglev::Maglev;
+use monad_load_balancer::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
+use monad_load_balancer::config::backend_cfg::BackendCfg;
+use monad_load_balancer::state::backend::Backend;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+const BACKENDS: usize = 10;
+const CLIENTS: u32 = 20_000;
+
+fn pool(n: usize) -> Vec<Arc<Backend>> {
+    (0..n)
+        .map(|i| {
+            let cfg: BackendCfg = serde_yaml::from_str(&format!(
+                "id: srv-{:02}\naddress: 127.0.0.1:{}\nmax_connections: 1000\nweight: 10",
+                i,
+                9000 + i
+            ))
+            .unwrap();
+            Arc::new(Backend::from_cfg(&cfg))
+        })
+        .collect()
+}
+
+fn client(i: u32) -> IpAddr {
+    IpAddr::V4(Ipv4Addr::from(0x0a00_0000 + i))
+}
+
+/// The id of the backend each client is sent to.
+fn assignments(
+    algorithm: &dyn LoadBalancingAlgorithm,
+    pool: &[Arc<Backend>],
+    eligible: &[Arc<Backend>],
+) -> Vec<String> {
+    (0..CLIENTS)
+        .map(|i| {
+            let idx = algorithm
+                .select_backend_for(pool, eligible, client(i))
+                .unwrap();
+            eligible[idx].id.clone()
+        })
+        .collect()
+}
+
+/// Removing one backend from the pool moves about 1/N of the clients, and at most
+/// `max_collateral` of them away from a backend that is still there.
+fn assert_minimal_remap_on_removal(algorithm: &dyn LoadBalancingAlgorithm, max_collateral: f64) {
+    let full = pool(BACKENDS);
+    let before = assignments(algorithm, &full, &full);
+
+    let removed = full[3].id.clone();
+    let shrunk: Vec<_> = full.iter().filter(|b| b.id != removed).cloned().collect();
+    let after = assignments(algorithm, &shrunk, &shrunk);
+
+    let moved = before
+        .iter()
+        .zip(&after)
+        .filter(|(old, new)| old != new)
+        .count();
+    let collateral = before
+        .iter()
+        .zip(&after)
+        .filter(|(old, new)| old != new && **old != removed)
+        .count();
+    assert!(
+        collateral as f64 / CLIENTS as f64 <= max_collateral,
+        "{} clients of remaining backends moved",
+        collateral
+    );
+
+    let share = moved as f64 / CLIENTS as f64;
+    let expected = 1.0 / BACKENDS as f64;
+    assert!(
+        (share - expected).abs() < expected * 0.5,
+        "moved {:.3} of the clients, expected about {:.3}",
+        share,
+        expected
+    );
+}
+
+/// A backend that is configured but ineligible only moves its own clients.
+fn assert_no_remap_when_ineligible(algorithm: &dyn LoadBalancingAlgorithm) {
+    let full = pool(BACKENDS);
+    let before = assignments(algorithm, &full, &full);
+
+    let down = full[3].id.clone();
+    let eligible: Vec<_> = full.iter().filter(|b| b.id != down).cloned().collect();
+    let after = assignments(algorithm, &full, &eligible);
+
+    for (old, new) in before.iter().zip(&after) {
+        assert_ne!(new, &down);
+        if old != &down {
+            assert_eq!(old, new, "a client of an eligible backend moved");
+        }
+    }
+}
+
+#[test]
+fn ring_remaps_about_one_nth_on_removal() {
+    assert_minimal_remap_on_removal(&ConsistentHash::new(), 0.0);
+}
+
+#[test]
+fn ring_keeps_other_clients_when_a_backend_is_ineligible() {
+    assert_no_remap_when_ineligible(&ConsistentHash::new());
+}
+
+#[test]
+fn ring_size_does_not_grow_with_the_weights() {
+    let pool = pool(3);
+    pool[0].base_weight.store(1_000_000_000, Ordering::Relaxed);
+    pool[0]
+        .current_weight
+        .store(1_000_000_000, Ordering::Relaxed);
+    // A ring sized by raw weight would not fit in memory; a bounded one still favours the
+    // heavy backend.
+    let ids = assignments(&ConsistentHash::new(), &pool, &pool);
+    let heavy = ids.iter().filter(|id| *id == &pool[0].id).count();
+    assert!(
+        heavy as f64 > 0.9 * CLIENTS as f64,
+        "{} of {} clients",
+        heavy,
+        CLIENTS
+    );
+}
+
+#[test]
+fn maglev_remaps_about_one_nth_on_removal() {
+    // Maglev trades a little disruption for its even spread; a rebuilt table moves a
+    // fraction of a percent of the other backends' clients.
+    assert_minimal_remap_on_removal(&Maglev::new(), 0.02);
+}
+
+#[test]
+fn maglev_keeps_other_clients_when_a_backend_is_ineligible() {
+    assert_no_remap_when_ineligible(&Maglev::new());
+}
+
+#[test]
+fn bounded_load_never_exceeds_capacity() {
+    const EPSILON: f64 = 0.25;
+    let algorithm = ConsistentHash::with_bounded_load(EPSILON);
+    let pool = pool(4);
+
+    // A handful of hot clients pile onto the same arcs of the ring.
+    for i in 0..400 {
+        let idx = algorithm
+            .select_backend_for(&pool, &pool, client(i % 3))
+            .unwrap();
+        pool[idx].active_conn.fetch_add(1, Ordering::Relaxed);
+
+        let total: u64 = pool
+            .iter()
+            .map(|b| b.active_conn.load(Ordering::Relaxed))
+            .sum();
+        let capacity = ((1.0 + EPSILON) * total as f64 / pool.len() as f64).ceil() as u64;
+        for backend in &pool {
+            let active = backend.active_conn.load(Ordering::Relaxed);
+            assert!(
+                active <= capacity,
+                "{} holds {} connections, capacity is {}",
+                backend.id,
+                active,
+                capacity
+            );
+        }
+    }
+}
+
+#[test]
+fn bounded_load_keeps_affinity_while_under_capacity() {
+    let algorithm = ConsistentHash::with_bounded_load(0.25);
+    let plain = ConsistentHash::new();
+    let pool = pool(BACKENDS);
+
+    for i in 0..1000 {
+        assert_eq!(
+            algorithm.select_backend_for(&pool, &pool, client(i)),
+            plain.select_backend_for(&pool, &pool, client(i))
+        );
+    }
+}
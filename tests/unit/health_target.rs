@@ -0,0 +1,27 @@
+use monad_load_balancer::config::backend_cfg::BackendCfg;
+
+fn target(extra: &str) -> String {
+    let yaml = format!("id: b1\naddress: 10.0.0.1:8080\nmax_connections: 10\nweight: 1\n{extra}");
+    serde_yaml::from_str::<BackendCfg>(&yaml)
+        .unwrap()
+        .health_target()
+}
+
+#[test]
+fn defaults_to_the_backend_address() {
+    assert_eq!(target(""), "10.0.0.1:8080");
+}
+
+#[test]
+fn health_port_replaces_the_port() {
+    assert_eq!(target("health_port: 9100"), "10.0.0.1:9100");
+}
+
+#[test]
+fn health_address_replaces_the_address() {
+    assert_eq!(target("health_address: 10.0.0.2:9000"), "10.0.0.2:9000");
+    assert_eq!(
+        target("health_address: 10.0.0.2:9000\nhealth_port: 9100"),
+        "10.0.0.2:9100"
+    );
+}
@@ -0,0 +1,12 @@
+mod agent_report;
+mod circuit_breaker;
+mod config_validation;
+mod connection_guard;
+mod error_window;
+mod hashing;
+mod health_target;
+mod p2c;
+mod peak_ewma;
+mod retry_cfg;
+mod slow_start;
+mod status_range;
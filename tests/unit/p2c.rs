@@ -0,0 +1,84 @@
+use monad_load_balancer::algorithms::core::p2c::P2c;
+use monad_load_balancer::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
+use monad_load_balancer::config::algorithm_cfg::P2cScore;
+use monad_load_balancer::config::backend_cfg::BackendCfg;
+use monad_load_balancer::state::backend::Backend;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+const PICKS: usize = 2_000;
+
+/// Backends with the given (outstanding connections, latency ms) loads.
+fn pool(loads: &[(u64, usize)]) -> Vec<Arc<Backend>> {
+    loads
+        .iter()
+        .enumerate()
+        .map(|(i, &(active_conn, latency_ms))| {
+            let cfg: BackendCfg = serde_yaml::from_str(&format!(
+                "id: srv-{}\naddress: 127.0.0.1:{}\nmax_connections: 1000\nweight: 10",
+                i,
+                9000 + i
+            ))
+            .unwrap();
+            let backend = Backend::from_cfg(&cfg);
+            backend.active_conn.store(active_conn, Ordering::Relaxed);
+            backend.avg_latency_ms.store(latency_ms, Ordering::Relaxed);
+            Arc::new(backend)
+        })
+        .collect()
+}
+
+fn picks(p2c: &P2c, pool: &[Arc<Backend>]) -> Vec<usize> {
+    let mut counts = vec![0; pool.len()];
+    for _ in 0..PICKS {
+        counts[p2c.select_backend(pool).unwrap()] += 1;
+    }
+    counts
+}
+
+#[test]
+fn handles_empty_and_single_pools() {
+    let p2c = P2c::default();
+    assert_eq!(p2c.select_backend(&[]), None);
+    assert_eq!(p2c.select_backend(&pool(&[(5, 5)])), Some(0));
+}
+
+#[test]
+fn of_two_backends_always_takes_the_lower_score() {
+    let cases = [
+        (P2cScore::Connections, pool(&[(4, 1), (1, 50)])),
+        (P2cScore::Latency, pool(&[(0, 40), (9, 5)])),
+        // 3 * 10 beats 1 * 50.
+        (P2cScore::Combined, pool(&[(0, 50), (2, 10)])),
+    ];
+    for (score, pool) in cases {
+        assert_eq!(picks(&P2c::new(score), &pool), [0, PICKS], "{:?}", score);
+    }
+}
+
+#[test]
+fn never_takes_the_most_loaded_backend() {
+    let pool = pool(&[(1, 5), (2, 5), (30, 5), (1, 5)]);
+    let counts = picks(&P2c::new(P2cScore::Connections), &pool);
+    assert_eq!(counts[2], 0);
+    assert!(
+        counts.iter().enumerate().all(|(i, &n)| i == 2 || n > 0),
+        "{:?}",
+        counts
+    );
+}
+
+#[test]
+fn spreads_evenly_across_equal_backends() {
+    let pool = pool(&[(0, 5); 4]);
+    let counts = picks(&P2c::default(), &pool);
+    let expected = PICKS / pool.len();
+    for n in counts {
+        assert!(
+            n.abs_diff(expected) < expected / 4,
+            "{} picks, expected about {}",
+            n,
+            expected
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use monad_load_balancer::algorithms::core::peak_ewma::PeakEwma;
+use monad_load_balancer::algorithms::traits::load_balancer_algorithm::LoadBalancingAlgorithm;
+use monad_load_balancer::config::backend_cfg::BackendCfg;
+use monad_load_balancer::state::backend::Backend;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+const DECAY: Duration = Duration::from_secs(10);
+
+fn pool(n: usize) -> Vec<Arc<Backend>> {
+    (0..n)
+        .map(|i| {
+            let cfg: BackendCfg = serde_yaml::from_str(&format!(
+                "id: srv-{}\naddress: 127.0.0.1:{}\nmax_connections: 1000\nweight: 10",
+                i,
+                9000 + i
+            ))
+            .unwrap();
+            Arc::new(Backend::from_cfg(&cfg))
+        })
+        .collect()
+}
+
+fn observe(ewma: &PeakEwma, backend: &Backend, latencies_ms: &[u64]) {
+    for &ms in latencies_ms {
+        ewma.observe_latency(backend, Duration::from_millis(ms));
+    }
+}
+
+#[test]
+fn prefers_the_lower_observed_latency() {
+    let ewma = PeakEwma::new(DECAY);
+    let pool = pool(2);
+    observe(&ewma, &pool[0], &[50, 50, 50]);
+    observe(&ewma, &pool[1], &[5, 5, 5]);
+    assert_eq!(ewma.select_backend(&pool), Some(1));
+}
+
+#[test]
+fn a_latency_spike_counts_at_once() {
+    let ewma = PeakEwma::new(DECAY);
+    let pool = pool(2);
+    observe(&ewma, &pool[0], &[5, 5, 5, 200]);
+    observe(&ewma, &pool[1], &[20, 20, 20]);
+    assert_eq!(ewma.select_backend(&pool), Some(1));
+}
+
+#[test]
+fn weighs_latency_by_outstanding_connections() {
+    let ewma = PeakEwma::new(DECAY);
+    let pool = pool(2);
+    observe(&ewma, &pool[0], &[10]);
+    observe(&ewma, &pool[1], &[25]);
+    // 10 ms * 4 outstanding against 25 ms * 1.
+    pool[0].active_conn.store(3, Ordering::Relaxed);
+    assert_eq!(ewma.select_backend(&pool), Some(1));
+}
+
+#[test]
+fn falls_back_to_the_probe_latency_before_any_measurement() {
+    let ewma = PeakEwma::new(DECAY);
+    let pool = pool(2);
+    pool[0].avg_latency_ms.store(30, Ordering::Relaxed);
+    pool[1].avg_latency_ms.store(3, Ordering::Relaxed);
+    assert_eq!(ewma.select_backend(&pool), Some(1));
+}
+
+#[test]
+fn unmeasured_backends_are_told_apart_by_outstanding_connections() {
+    let ewma = PeakEwma::new(DECAY);
+    let pool = pool(3);
+    pool[0].active_conn.store(2, Ordering::Relaxed);
+    pool[1].active_conn.store(1, Ordering::Relaxed);
+    assert_eq!(ewma.select_backend(&pool), Some(2));
+
+    pool[2].active_conn.store(5, Ordering::Relaxed);
+    assert_eq!(ewma.select_backend(&pool), Some(1));
+}
@@ -0,0 +1,76 @@
+use monad_load_balancer::config::retry_cfg::{RetryCfg, RetryOverrideCfg};
+use std::time::Duration;
+
+fn policy(jitter: f64) -> RetryCfg {
+    RetryCfg {
+        max_attempts: 5,
+        initial_delay_ms: 100,
+        backoff_factor: 2.0,
+        max_delay_ms: 1000,
+        jitter,
+        deadline_ms: 0,
+    }
+}
+
+#[test]
+fn delay_grows_by_the_backoff_factor() {
+    let policy = policy(0.0);
+    assert_eq!(policy.delay(1), Duration::from_millis(100));
+    assert_eq!(policy.delay(2), Duration::from_millis(200));
+    assert_eq!(policy.delay(3), Duration::from_millis(400));
+}
+
+#[test]
+fn delay_is_capped_at_max_delay() {
+    let policy = policy(0.0);
+    assert_eq!(policy.delay(5), Duration::from_millis(1000));
+    assert_eq!(policy.delay(60), Duration::from_millis(1000));
+}
+
+#[test]
+fn jitter_only_shortens_the_delay() {
+    let policy = policy(0.5);
+    for _ in 0..200 {
+        let delay = policy.delay(2);
+        assert!(delay <= Duration::from_millis(200), "{:?}", delay);
+        assert!(delay >= Duration::from_millis(100), "{:?}", delay);
+    }
+}
+
+#[test]
+fn override_replaces_only_the_fields_it_sets() {
+    let base = policy(0.2);
+    let over = RetryOverrideCfg {
+        initial_delay_ms: Some(10),
+        max_delay_ms: Some(50),
+        ..Default::default()
+    };
+
+    let merged = base.with_override(Some(&over));
+    assert_eq!(merged.initial_delay_ms, 10);
+    assert_eq!(merged.max_delay_ms, 50);
+    assert_eq!(merged.backoff_factor, base.backoff_factor);
+    assert_eq!(merged.jitter, base.jitter);
+    assert_eq!(merged.max_attempts, base.max_attempts);
+    assert_eq!(merged.deadline_ms, base.deadline_ms);
+}
+
+#[test]
+fn no_override_keeps_the_policy() {
+    let base = policy(0.0);
+    let merged = base.with_override(None);
+    assert_eq!(merged.delay(3), base.delay(3));
+    assert_eq!(merged.max_attempts, base.max_attempts);
+}
+
+#[test]
+fn missing_fields_take_their_defaults() {
+    let cfg: RetryCfg = serde_yaml::from_str("max_attempts: 5").unwrap();
+    let defaults = RetryCfg::default();
+    assert_eq!(cfg.max_attempts, 5);
+    assert_eq!(cfg.initial_delay_ms, defaults.initial_delay_ms);
+    assert_eq!(cfg.backoff_factor, defaults.backoff_factor);
+    assert_eq!(cfg.max_delay_ms, defaults.max_delay_ms);
+    assert_eq!(cfg.jitter, defaults.jitter);
+    assert_eq!(cfg.deadline_ms, defaults.deadline_ms);
+}
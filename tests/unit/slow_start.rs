@@ -0,0 +1,44 @@
+use monad_load_balancer::config::backend_cfg::BackendCfg;
+use monad_load_balancer::config::thresholds_cfg::SlowStartCurve;
+use monad_load_balancer::state::backend::Backend;
+use std::time::Duration;
+
+fn backend(weight: u64) -> Backend {
+    let cfg: BackendCfg = serde_yaml::from_str(&format!(
+        "id: b1\naddress: 127.0.0.1:9001\nmax_connections: 10\nweight: {weight}"
+    ))
+    .unwrap();
+    Backend::from_cfg(&cfg)
+}
+
+#[test]
+fn curves_run_from_zero_to_one() {
+    for curve in [SlowStartCurve::Linear, SlowStartCurve::Exponential] {
+        assert_eq!(curve.factor(0.0), 0.0);
+        assert_eq!(curve.factor(1.0), 1.0);
+        assert_eq!(curve.factor(2.0), 1.0);
+    }
+    assert_eq!(SlowStartCurve::Linear.factor(0.5), 0.5);
+    assert!(SlowStartCurve::Exponential.factor(0.5) < 0.05);
+}
+
+#[test]
+fn a_backend_outside_its_window_gets_its_full_share() {
+    let backend = backend(10);
+    backend.refresh_traffic_share(Duration::from_secs(60), SlowStartCurve::Linear);
+    assert_eq!(backend.traffic_share(), 1.0);
+    assert_eq!(backend.effective_weight(), 10);
+}
+
+#[test]
+fn a_starting_backend_is_scaled_down_but_keeps_some_weight() {
+    let backend = backend(10);
+    backend.start_slow_start();
+    backend.refresh_traffic_share(Duration::from_secs(60), SlowStartCurve::Linear);
+    assert!(backend.traffic_share() < 0.01);
+    assert_eq!(backend.effective_weight(), 1);
+
+    // A disabled ramp never scales.
+    backend.refresh_traffic_share(Duration::ZERO, SlowStartCurve::Linear);
+    assert_eq!(backend.effective_weight(), 10);
+}
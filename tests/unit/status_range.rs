@@ -0,0 +1,69 @@
+use monad_load_balancer::config::health_check_cfg::StatusRange;
+
+fn parse(yaml: &str) -> Result<StatusRange, serde_yaml::Error> {
+    serde_yaml::from_str(yaml)
+}
+
+#[test]
+fn parses_a_single_code() {
+    let range = parse("204").unwrap();
+    assert_eq!((range.start, range.end), (204, 204));
+    assert!(range.contains(204));
+    assert!(!range.contains(200));
+}
+
+#[test]
+fn parses_a_range() {
+    let range = parse("\"200-399\"").unwrap();
+    assert_eq!((range.start, range.end), (200, 399));
+    assert!(range.contains(200));
+    assert!(range.contains(399));
+    assert!(!range.contains(400));
+}
+
+#[test]
+fn parses_a_quoted_single_code_and_spaces() {
+    assert_eq!(
+        parse("\"503\"").unwrap(),
+        StatusRange {
+            start: 503,
+            end: 503
+        }
+    );
+    assert_eq!(
+        parse("\"200 - 299\"").unwrap(),
+        StatusRange {
+            start: 200,
+            end: 299
+        }
+    );
+}
+
+#[test]
+fn rejects_garbage() {
+    assert!(parse("\"ok\"").is_err());
+    assert!(parse("\"200-\"").is_err());
+    assert!(parse("\"2xx\"").is_err());
+}
+
+#[test]
+fn serializes_back_to_the_short_form() {
+    assert_eq!(
+        serde_yaml::to_string(&StatusRange {
+            start: 200,
+            end: 200
+        })
+        .unwrap()
+        .trim(),
+        "200"
+    );
+    assert_eq!(
+        serde_yaml::to_string(&StatusRange {
+            start: 200,
+            end: 399
+        })
+        .unwrap()
+        .trim(),
+        "200-399"
+    );
+}